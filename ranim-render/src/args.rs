@@ -1,8 +1,13 @@
 use std::{fmt::Display, path::PathBuf, str::FromStr};
 
 use clap::Parser;
+use ranim::{color::Color, scene::SceneConfig};
 
-use crate::util::Size;
+use crate::{
+    codec::{Bitrate, Codec, EncoderConfig},
+    util::Size,
+    Antialiasing,
+};
 
 /// Renderer frontend of `ranim`
 #[derive(Parser, Debug)]
@@ -20,15 +25,127 @@ pub struct Args {
     #[clap(short, long, default_value_t = Quality::Low)]
     pub quality: Quality,
 
+    /// The file to write to. A `.png` file is written as a sequence of numbered PNG images, one
+    /// per frame, and `.gif`, `.apng` and `.webp` files as looping animations. Anything else is
+    /// encoded as a video, see `--codec`.
     #[clap(short, long, default_value = "media/output")]
     pub output_file: PathBuf,
 
+    /// The video codec: h264, hevc, vp9, av1, prores or ffv1. Defaults to the usual codec of the
+    /// output container: vp9 for `.webm`, prores for `.mov` and h264 otherwise. Without an
+    /// extension, the output file gets the usual container of the codec.
+    #[clap(long)]
+    pub codec: Option<Codec>,
+
+    /// Constant quality on the scale of the encoder, lower being better: `crf` for x264, x265,
+    /// VP9 and AV1, `cq` for NVENC.
+    #[clap(long, conflicts_with = "bitrate")]
+    pub crf: Option<f32>,
+
+    /// Target bitrate in place of constant quality, like `8M` or `2500k`.
+    #[clap(long)]
+    pub bitrate: Option<Bitrate>,
+
+    /// Renders the scene twice, the first time only for the encoder to plan where to spend bits,
+    /// for better quality at a given bitrate. Works with x264, VP9 and libaom's AV1.
+    #[clap(long)]
+    pub two_pass: bool,
+
+    /// Encoder speed preset, like `slow` for x264 or `p7` for NVENC.
+    #[clap(long)]
+    pub preset: Option<String>,
+
+    /// Encoder tuning, like `animation` for x264.
+    #[clap(long)]
+    pub tune: Option<String>,
+
+    /// Maximum number of frames from one keyframe to the next.
+    #[clap(long, default_value_t = 10)]
+    pub gop: u32,
+
+    /// Maximum number of B-frames in a row.
+    #[clap(long, default_value_t = 1)]
+    pub b_frames: u32,
+
+    /// An FFmpeg option for the encoder as `key=value`, overriding all others. Can be given more
+    /// than once.
+    #[clap(
+        long = "encoder-option",
+        value_name = "KEY=VALUE",
+        parse(try_from_str = parse_key_value),
+        multiple_occurrences = true
+    )]
+    pub encoder_options: Vec<(String, String)>,
+
+    /// The background color of the scene, as a palette name like `dark-gray` or a hex code like
+    /// `#1e1e1e`. `transparent` leaves the background empty in output formats with an alpha
+    /// channel: images, animations, and ProRes or VP9 video.
+    #[clap(short, long, default_value = "black")]
+    pub background: Color,
+
+    /// Samples per pixel for multisample antialiasing: 1 to turn it off, 2, 4 or 8.
+    #[clap(long, default_value_t = 4, possible_values = ["1", "2", "4", "8"])]
+    pub msaa: u32,
+
+    /// Renders frames at 2 or 4 times the output resolution and filters them down, for smoother
+    /// edges and thin lines than MSAA alone at a higher cost.
+    #[clap(long, default_value_t = 1, possible_values = ["1", "2", "4"])]
+    pub supersample: u32,
+
+    /// How GIF output picks its colors: `per-frame` finds the best 256 colors for each frame,
+    /// `global` shares one palette across all frames so colors don't flicker between them.
+    #[clap(long, default_value_t = GifPalette::PerFrame)]
+    pub gif_palette: GifPalette,
+
+    /// Dithers GIF output, trading smooth gradients for a little noise.
+    #[clap(long)]
+    pub dither: bool,
+
+    /// Writes only the last frame of the scene, as a PNG image.
     #[clap(long)]
     pub single_frame: bool,
 
+    /// Renders the scene without writing anything.
     #[clap(long)]
     pub no_output: bool
 }
+impl Args {
+    /// The scene settings matching the output of this run.
+    pub fn scene_config(&self) -> SceneConfig {
+        let size = self.quality.size();
+        SceneConfig {
+            frame_rate: self.quality.frame_rate(),
+            width: size.width,
+            height: size.height,
+            background: self.background,
+        }
+    }
+    pub fn encoder_config(&self) -> EncoderConfig {
+        EncoderConfig {
+            crf: self.crf,
+            bitrate: self.bitrate,
+            two_pass: self.two_pass,
+            preset: self.preset.clone(),
+            tune: self.tune.clone(),
+            gop: self.gop,
+            b_frames: self.b_frames,
+            options: self.encoder_options.clone(),
+        }
+    }
+    pub fn antialiasing(&self) -> Antialiasing {
+        Antialiasing {
+            msaa: self.msaa,
+            supersample: self.supersample,
+        }
+    }
+}
+
+fn parse_key_value(s: &str) -> Result<(String, String), String> {
+    match s.split_once('=') {
+        Some((key, value)) if !key.is_empty() => Ok((key.to_string(), value.to_string())),
+        _ => Err(format!("Expected key=value: {s}")),
+    }
+}
 
 #[derive(Debug, Clone, Copy)]
 pub enum Quality {
@@ -75,3 +192,79 @@ impl FromStr for Quality {
         }
     }
 }
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GifPalette {
+    PerFrame,
+    Global,
+}
+impl Display for GifPalette {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GifPalette::PerFrame => write!(f, "per-frame"),
+            GifPalette::Global => write!(f, "global"),
+        }
+    }
+}
+impl FromStr for GifPalette {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "per-frame" | "local" => Ok(Self::PerFrame),
+            "global" => Ok(Self::Global),
+            _ => Err(format!("Invalid GIF palette: {s}")),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &[&str]) -> clap::Result<Args> {
+        Args::try_parse_from(["ranim"].iter().chain(args))
+    }
+
+    #[test]
+    fn quality_flags() {
+        let args = parse(&["--bitrate", "2.5M"]).unwrap();
+        assert_eq!(args.encoder_config().bitrate, Some(Bitrate(2_500_000)));
+        assert_eq!(args.encoder_config().crf, None);
+
+        let args = parse(&["--crf", "18"]).unwrap();
+        assert_eq!(args.encoder_config().crf, Some(18.0));
+
+        assert!(parse(&["--crf", "18", "--bitrate", "2M"]).is_err());
+        assert!(parse(&["--bitrate", "fast"]).is_err());
+    }
+
+    #[test]
+    fn encoder_options() {
+        let args = parse(&[
+            "--encoder-option",
+            "tune=film",
+            "--encoder-option",
+            "x264-params=keyint=60:min-keyint=60",
+        ])
+        .unwrap();
+        assert_eq!(
+            args.encoder_config().options,
+            [
+                ("tune".to_string(), "film".to_string()),
+                (
+                    "x264-params".to_string(),
+                    "keyint=60:min-keyint=60".to_string()
+                ),
+            ]
+        );
+        assert!(parse(&["--encoder-option", "tune"]).is_err());
+        assert!(parse(&["--encoder-option", "=film"]).is_err());
+    }
+
+    #[test]
+    fn key_value() {
+        assert_eq!(parse_key_value("b="), Ok(("b".to_string(), String::new())));
+        assert!(parse_key_value("").is_err());
+    }
+}
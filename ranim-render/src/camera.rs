@@ -18,7 +18,7 @@ impl Camera2D {
         let mut cam = Self {
             position: Vec3::ZERO,
             rotation: 0.0,
-            scale: 1.0 / 7.0,
+            scale: 2.0 / ranim::scene::FRAME_WIDTH,
             aspect: 1.0,
         };
         cam.resize(size);
@@ -0,0 +1,463 @@
+use std::{ffi::CString, fmt::Display, str::FromStr};
+
+use rsmpeg::{
+    avcodec::{AVCodec, AVCodecRef},
+    avutil::AVPixelFormat,
+    ffi::{self, AVCodecID},
+};
+
+/// The video codecs ranim can encode with, each through the first of its FFmpeg encoders that
+/// the local FFmpeg build has and can open.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Codec {
+    H264,
+    Hevc,
+    Vp9,
+    Av1,
+    ProRes,
+    Ffv1,
+}
+impl Codec {
+    pub const ALL: [Codec; 6] = [
+        Codec::H264,
+        Codec::Hevc,
+        Codec::Vp9,
+        Codec::Av1,
+        Codec::ProRes,
+        Codec::Ffv1,
+    ];
+
+    /// The codec for a container picked by its file extension, for when none is given.
+    pub fn for_container(extension: &str) -> Self {
+        match extension.to_ascii_lowercase().as_str() {
+            "webm" => Codec::Vp9,
+            "mov" => Codec::ProRes,
+            _ => Codec::H264,
+        }
+    }
+    /// The file extension of the container for this codec, for when the output file has none.
+    pub fn default_container(self) -> &'static str {
+        match self {
+            Codec::H264 | Codec::Hevc | Codec::Av1 => "mp4",
+            Codec::Vp9 => "webm",
+            Codec::ProRes => "mov",
+            Codec::Ffv1 => "mkv",
+        }
+    }
+
+    /// FFmpeg encoders for this codec, most preferred first.
+    pub fn encoders(self) -> &'static [Encoder] {
+        match self {
+            Codec::H264 => H264_ENCODERS,
+            Codec::Hevc => HEVC_ENCODERS,
+            Codec::Vp9 => VP9_ENCODERS,
+            Codec::Av1 => AV1_ENCODERS,
+            Codec::ProRes => PRORES_ENCODERS,
+            Codec::Ffv1 => FFV1_ENCODERS,
+        }
+    }
+    pub fn id(self) -> AVCodecID {
+        match self {
+            Codec::H264 => ffi::AVCodecID_AV_CODEC_ID_H264,
+            Codec::Hevc => ffi::AVCodecID_AV_CODEC_ID_HEVC,
+            Codec::Vp9 => ffi::AVCodecID_AV_CODEC_ID_VP9,
+            Codec::Av1 => ffi::AVCodecID_AV_CODEC_ID_AV1,
+            Codec::ProRes => ffi::AVCodecID_AV_CODEC_ID_PRORES,
+            Codec::Ffv1 => ffi::AVCodecID_AV_CODEC_ID_FFV1,
+        }
+    }
+    /// Options to add to the defaults of each encoder to encode alpha.
+    fn alpha_options(self) -> &'static str {
+        match self {
+            Codec::ProRes => "profile=4444",
+            _ => "",
+        }
+    }
+    /// Whether this codec can keep an alpha channel: ProRes as 4444 and VP9 as `yuva420p`.
+    pub fn supports_alpha(self) -> bool {
+        matches!(self, Codec::ProRes | Codec::Vp9)
+    }
+    /// The pixel format frames are encoded in, with alpha if asked for and supported.
+    pub fn yuv_format(self, alpha: bool) -> YuvFormat {
+        match self {
+            Codec::Vp9 if alpha => YuvFormat::Yuva420,
+            Codec::ProRes if alpha => YuvFormat::Yuva444P10,
+            Codec::H264 | Codec::Hevc | Codec::Vp9 | Codec::Av1 => YuvFormat::Yuv420,
+            Codec::ProRes => YuvFormat::Yuv422P10,
+            Codec::Ffv1 => YuvFormat::Yuv444,
+        }
+    }
+
+    /// The encoders of every codec that the local FFmpeg build has, like
+    /// `h264 (libx264), vp9 (libvpx-vp9)`.
+    pub fn available_encoders() -> String {
+        let available: Vec<String> = Codec::ALL
+            .iter()
+            .filter_map(|codec| {
+                let names: Vec<_> = codec
+                    .encoders()
+                    .iter()
+                    .map(|encoder| encoder.name)
+                    .filter(|name| find_encoder(name).is_some())
+                    .collect();
+                (!names.is_empty()).then(|| format!("{codec} ({})", names.join(", ")))
+            })
+            .collect();
+        match available.is_empty() {
+            true => "none".to_string(),
+            false => available.join(", "),
+        }
+    }
+}
+const H264_ENCODERS: &[Encoder] = &[
+    Encoder::new("h264_nvenc", "rc=vbr,cq=28,profile=high,preset=fast")
+        .quality_option("cq"),
+    Encoder::new("libx264", "crf=28,profile=high,preset=fast")
+        .quality_option("crf")
+        .two_pass(TwoPass::StatsFile),
+];
+const HEVC_ENCODERS: &[Encoder] = &[
+    Encoder::new("hevc_nvenc", "rc=vbr,cq=30,preset=fast").quality_option("cq"),
+    Encoder::new("libx265", "crf=28,preset=fast").quality_option("crf"),
+];
+const VP9_ENCODERS: &[Encoder] = &[
+    Encoder::new("libvpx-vp9", "crf=32,b=0,deadline=good,cpu-used=4,row-mt=1")
+        .quality_option("crf")
+        .two_pass(TwoPass::Stats),
+];
+const AV1_ENCODERS: &[Encoder] = &[
+    Encoder::new("libsvtav1", "crf=35,preset=8").quality_option("crf"),
+    Encoder::new("libaom-av1", "crf=32,b=0,cpu-used=6,row-mt=1")
+        .quality_option("crf")
+        .two_pass(TwoPass::Stats),
+];
+const PRORES_ENCODERS: &[Encoder] = &[
+    Encoder::new("prores_ks", "profile=standard,vendor=apl0"),
+    Encoder::new("prores_aw", "profile=standard,vendor=apl0"),
+];
+const FFV1_ENCODERS: &[Encoder] = &[Encoder::new("ffv1", "level=3,slices=4,slicecrc=1")];
+
+/// An FFmpeg encoder of a [`Codec`].
+#[derive(Debug)]
+pub struct Encoder {
+    pub name: &'static str,
+    /// Default options, as `key=value` pairs separated by commas.
+    pub options: &'static str,
+    /// The option setting constant quality, if the encoder has one.
+    pub quality_option: Option<&'static str>,
+    pub two_pass: TwoPass,
+}
+impl Encoder {
+    const fn new(name: &'static str, options: &'static str) -> Self {
+        Self {
+            name,
+            options,
+            quality_option: None,
+            two_pass: TwoPass::Unsupported,
+        }
+    }
+    const fn quality_option(mut self, option: &'static str) -> Self {
+        self.quality_option = Some(option);
+        self
+    }
+    const fn two_pass(mut self, two_pass: TwoPass) -> Self {
+        self.two_pass = two_pass;
+        self
+    }
+}
+
+/// How an encoder carries what it learned in the first of two passes over to the second.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TwoPass {
+    Unsupported,
+    /// Through `stats_out` after the first pass and `stats_in` for the second.
+    Stats,
+    /// Through a file it writes and reads itself, named by its `stats` option.
+    StatsFile,
+}
+
+/// Encoder settings from the command line, applied over the defaults of each encoder.
+#[derive(Debug, Clone)]
+pub struct EncoderConfig {
+    /// Constant quality on the scale of the encoder.
+    pub crf: Option<f32>,
+    /// Target bitrate, in place of constant quality.
+    pub bitrate: Option<Bitrate>,
+    pub two_pass: bool,
+    pub preset: Option<String>,
+    pub tune: Option<String>,
+    /// Maximum distance between keyframes, in frames.
+    pub gop: u32,
+    /// Maximum number of B-frames in a row.
+    pub b_frames: u32,
+    /// Options passed to the encoder as they are, after all others.
+    pub options: Vec<(String, String)>,
+}
+impl EncoderConfig {
+    /// The options to open `encoder` of `codec` with: its defaults, those for alpha if `alpha`,
+    /// then this config. Later options replace earlier ones with the same key.
+    pub fn options(&self, codec: Codec, encoder: &Encoder, alpha: bool) -> Vec<(String, String)> {
+        fn set(options: &mut Vec<(String, String)>, key: &str, value: String) {
+            options.retain(|(k, _)| k != key);
+            options.push((key.to_string(), value));
+        }
+
+        let mut options = vec![];
+        let alpha_options = if alpha { codec.alpha_options() } else { "" };
+        for pair in encoder.options.split(',').chain(alpha_options.split(',')) {
+            if let Some((key, value)) = pair.split_once('=') {
+                set(&mut options, key, value.to_string());
+            }
+        }
+
+        if let Some(crf) = self.crf {
+            match encoder.quality_option {
+                Some(key) => set(&mut options, key, crf.to_string()),
+                None => log::warn!("{} has no constant quality, so --crf is ignored", encoder.name),
+            }
+        }
+        if let Some(bitrate) = self.bitrate {
+            if let Some(key) = encoder.quality_option {
+                options.retain(|(k, _)| k != key);
+            }
+            set(&mut options, "b", bitrate.0.to_string());
+        }
+        if let Some(preset) = &self.preset {
+            set(&mut options, "preset", preset.clone());
+        }
+        if let Some(tune) = &self.tune {
+            set(&mut options, "tune", tune.clone());
+        }
+        for (key, value) in &self.options {
+            set(&mut options, key, value.clone());
+        }
+        options
+    }
+}
+
+/// A bitrate in bits per second, written as a number with an optional `k`, `M` or `G` suffix.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Bitrate(pub u64);
+impl FromStr for Bitrate {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (number, scale) = match s.chars().last() {
+            Some('k' | 'K') => (&s[..s.len() - 1], 1e3),
+            Some('m' | 'M') => (&s[..s.len() - 1], 1e6),
+            Some('g' | 'G') => (&s[..s.len() - 1], 1e9),
+            _ => (s, 1.0),
+        };
+        let bits = number.parse::<f64>().map(|number| (number * scale).round());
+        match bits {
+            Ok(bits) if bits > 0.0 && bits.is_finite() => Ok(Self(bits as u64)),
+            _ => Err(format!("Invalid bitrate: {s}")),
+        }
+    }
+}
+
+/// The FFmpeg encoder called `name`, if this build has it.
+pub fn find_encoder(name: &str) -> Option<AVCodecRef<'static>> {
+    AVCodec::find_encoder_by_name(&CString::new(name).unwrap())
+}
+
+impl Display for Codec {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            Codec::H264 => "h264",
+            Codec::Hevc => "hevc",
+            Codec::Vp9 => "vp9",
+            Codec::Av1 => "av1",
+            Codec::ProRes => "prores",
+            Codec::Ffv1 => "ffv1",
+        };
+        write!(f, "{name}")
+    }
+}
+impl FromStr for Codec {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "h264" | "avc" | "x264" => Ok(Self::H264),
+            "hevc" | "h265" | "x265" => Ok(Self::Hevc),
+            "vp9" => Ok(Self::Vp9),
+            "av1" => Ok(Self::Av1),
+            "prores" => Ok(Self::ProRes),
+            "ffv1" => Ok(Self::Ffv1),
+            _ => Err(format!("Invalid codec: {s}")),
+        }
+    }
+}
+
+/// Planar YUV layouts filled from the YUV texture, which has full resolution chroma.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum YuvFormat {
+    /// 8 bits, chroma at half width and height.
+    Yuv420,
+    /// 10 bits, chroma at half width.
+    Yuv422P10,
+    /// 8 bits, chroma at full resolution.
+    Yuv444,
+    /// [`Yuv420`](Self::Yuv420) with full resolution alpha.
+    Yuva420,
+    /// 10 bits, chroma and alpha at full resolution.
+    Yuva444P10,
+}
+impl YuvFormat {
+    pub fn pix_fmt(self) -> AVPixelFormat {
+        match self {
+            YuvFormat::Yuv420 => ffi::AVPixelFormat_AV_PIX_FMT_YUV420P,
+            YuvFormat::Yuv422P10 => ffi::AVPixelFormat_AV_PIX_FMT_YUV422P10LE,
+            YuvFormat::Yuv444 => ffi::AVPixelFormat_AV_PIX_FMT_YUV444P,
+            YuvFormat::Yuva420 => ffi::AVPixelFormat_AV_PIX_FMT_YUVA420P,
+            YuvFormat::Yuva444P10 => ffi::AVPixelFormat_AV_PIX_FMT_YUVA444P10LE,
+        }
+    }
+    /// How many pixels across and down share one chroma sample.
+    pub fn chroma_block(self) -> (usize, usize) {
+        match self {
+            YuvFormat::Yuv420 | YuvFormat::Yuva420 => (2, 2),
+            YuvFormat::Yuv422P10 => (2, 1),
+            YuvFormat::Yuv444 | YuvFormat::Yuva444P10 => (1, 1),
+        }
+    }
+    /// Whether samples take 16 bits, little-endian, rather than 8.
+    pub fn is_wide(self) -> bool {
+        matches!(self, YuvFormat::Yuv422P10 | YuvFormat::Yuva444P10)
+    }
+    /// Whether frames have a fourth plane with alpha, at full resolution.
+    pub fn has_alpha(self) -> bool {
+        matches!(self, YuvFormat::Yuva420 | YuvFormat::Yuva444P10)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config() -> EncoderConfig {
+        EncoderConfig {
+            crf: None,
+            bitrate: None,
+            two_pass: false,
+            preset: None,
+            tune: None,
+            gop: 10,
+            b_frames: 1,
+            options: vec![],
+        }
+    }
+
+    fn option<'a>(options: &'a [(String, String)], key: &str) -> Option<&'a str> {
+        options.iter().find(|(k, _)| k == key).map(|(_, v)| v.as_str())
+    }
+
+    #[test]
+    fn bitrate_suffixes() {
+        for (s, bits) in [
+            ("800", 800),
+            ("500k", 500_000),
+            ("500K", 500_000),
+            ("2M", 2_000_000),
+            ("2.5m", 2_500_000),
+            ("1G", 1_000_000_000),
+        ] {
+            assert_eq!(s.parse(), Ok(Bitrate(bits)), "{s}");
+        }
+    }
+
+    #[test]
+    fn bad_bitrates() {
+        for s in ["", "k", "M", "0", "-1M", "2MB", "two", "1 M", "inf", "NaN", "0.4"] {
+            assert_eq!(
+                s.parse::<Bitrate>(),
+                Err(format!("Invalid bitrate: {s}")),
+                "{s}"
+            );
+        }
+    }
+
+    #[test]
+    fn encoder_defaults() {
+        let options = config().options(Codec::Vp9, &VP9_ENCODERS[0], false);
+        assert_eq!(option(&options, "crf"), Some("32"));
+        assert_eq!(option(&options, "b"), Some("0"));
+        assert_eq!(option(&options, "row-mt"), Some("1"));
+    }
+
+    #[test]
+    fn crf_replaces_encoder_quality() {
+        let config = EncoderConfig {
+            crf: Some(20.0),
+            ..config()
+        };
+        let nvenc = config.options(Codec::H264, &H264_ENCODERS[0], false);
+        assert_eq!(option(&nvenc, "cq"), Some("20"));
+        assert_eq!(option(&nvenc, "crf"), None);
+        assert_eq!(option(&nvenc, "rc"), Some("vbr"));
+
+        let x264 = config.options(Codec::H264, &H264_ENCODERS[1], false);
+        assert_eq!(option(&x264, "crf"), Some("20"));
+
+        // VP9 only uses constant quality with a zero bitrate
+        let vp9 = config.options(Codec::Vp9, &VP9_ENCODERS[0], false);
+        assert_eq!(option(&vp9, "crf"), Some("20"));
+        assert_eq!(option(&vp9, "b"), Some("0"));
+
+        // no quality option to set, so the defaults stay
+        let prores = config.options(Codec::ProRes, &PRORES_ENCODERS[0], false);
+        assert_eq!(option(&prores, "crf"), None);
+    }
+
+    #[test]
+    fn bitrate_replaces_encoder_quality() {
+        let config = EncoderConfig {
+            bitrate: Some(Bitrate(2_000_000)),
+            ..config()
+        };
+        let vp9 = config.options(Codec::Vp9, &VP9_ENCODERS[0], false);
+        assert_eq!(option(&vp9, "b"), Some("2000000"));
+        assert_eq!(option(&vp9, "crf"), None);
+
+        let nvenc = config.options(Codec::H264, &H264_ENCODERS[0], false);
+        assert_eq!(option(&nvenc, "b"), Some("2000000"));
+        assert_eq!(option(&nvenc, "cq"), None);
+    }
+
+    #[test]
+    fn later_options_win() {
+        let config = EncoderConfig {
+            crf: Some(18.0),
+            preset: Some("slow".to_string()),
+            tune: Some("animation".to_string()),
+            options: vec![
+                ("crf".to_string(), "30".to_string()),
+                ("profile".to_string(), "main".to_string()),
+            ],
+            ..config()
+        };
+        let x264 = config.options(Codec::H264, &H264_ENCODERS[1], false);
+        assert_eq!(option(&x264, "preset"), Some("slow"));
+        assert_eq!(option(&x264, "tune"), Some("animation"));
+        assert_eq!(option(&x264, "crf"), Some("30"));
+        assert_eq!(option(&x264, "profile"), Some("main"));
+        // each key is set once
+        assert_eq!(x264.iter().filter(|(k, _)| k == "crf").count(), 1);
+
+        let prores = EncoderConfig {
+            options: vec![("profile".to_string(), "hq".to_string())],
+            ..config
+        }
+        .options(Codec::ProRes, &PRORES_ENCODERS[0], true);
+        assert_eq!(option(&prores, "profile"), Some("hq"));
+    }
+
+    #[test]
+    fn alpha_options() {
+        let prores = config().options(Codec::ProRes, &PRORES_ENCODERS[0], true);
+        assert_eq!(option(&prores, "profile"), Some("4444"));
+        let opaque = config().options(Codec::ProRes, &PRORES_ENCODERS[0], false);
+        assert_eq!(option(&opaque, "profile"), Some("standard"));
+    }
+}
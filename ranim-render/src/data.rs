@@ -1,18 +1,64 @@
+use std::ops::Range;
+
+use ranim::{
+    color::{BlendMode, Color},
+    frame::{Frame, Mesh},
+};
+
 use crate::{camera::CameraGroup, Renderer};
 
 use self::{
     buffer::DynamicBuffer,
-    types::{Index, InstanceRaw, Vertex},
+    types::{Index, Instance, InstanceRaw, Vertex, WideIndex},
 };
 
 pub mod buffer;
 pub mod types;
 
+/// Identifies a mesh registered with [`RenderData::register_mesh`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct MeshId(usize);
+
+/// A registered mesh and the instances it is drawn with.
+struct MeshEntry {
+    indices: Range<u32>,
+    base_vertex: i32,
+    blend_mode: BlendMode,
+    instances: Vec<InstanceRaw>,
+}
+
+/// One mesh with all its instances, drawn in one call.
+pub struct Draw {
+    pub indices: Range<u32>,
+    /// First vertex of the mesh, which its indices count from.
+    pub base_vertex: i32,
+    pub instances: Range<u32>,
+    pub blend_mode: BlendMode,
+}
+
+/// Draw data of one frame.
+///
+/// Meshes are registered once and then drawn with any number of instances, each placing and
+/// tinting the whole mesh, so a mesh such as a glyph can be reused across a frame and across
+/// frames. Meshes are drawn in the order they were registered, each with its instances in the
+/// order they were added.
+///
+/// Each mesh is drawn as its own batch, with indices relative to its first vertex, so a frame can
+/// have any number of vertices with 16-bit indices. Only when a single mesh has more vertices
+/// than those can address does the frame switch to 32-bit indices.
 pub struct RenderData {
     pub vertices: DynamicBuffer<Vertex>,
     pub indices: DynamicBuffer<Index>,
+    pub wide_indices: DynamicBuffer<WideIndex>,
+    /// Which of `indices` and `wide_indices` the current frame uses.
+    pub index_format: wgpu::IndexFormat,
     pub instances: DynamicBuffer<InstanceRaw>,
     pub camera: CameraGroup,
+    meshes: Vec<MeshEntry>,
+    /// The meshes to draw, back to front, as of the last [`update`](Self::update).
+    pub draws: Vec<Draw>,
+    /// Color the frame is cleared to.
+    pub background: Color,
 }
 impl RenderData {
     pub fn new(renderer: &Renderer) -> Self {
@@ -26,6 +72,11 @@ impl RenderData {
             Some("Index Buffer"),
             wgpu::BufferUsages::INDEX,
         );
+        let wide_indices = DynamicBuffer::new(
+            &renderer.device,
+            Some("Wide Index Buffer"),
+            wgpu::BufferUsages::INDEX,
+        );
         let instances = DynamicBuffer::new(
             &renderer.device,
             Some("Instance Buffer"),
@@ -37,14 +88,121 @@ impl RenderData {
         Self {
             vertices,
             indices,
+            wide_indices,
+            index_format: wgpu::IndexFormat::Uint16,
             instances,
             camera,
+            meshes: vec![],
+            draws: vec![],
+            background: Color::TRANSPARENT,
+        }
+    }
+    /// Replaces the current draw data with the meshes of `frame`, each
+    /// drawn once.
+    pub fn load_frame(&mut self, frame: &Frame) {
+        self.clear_meshes();
+        self.background = frame.background;
+
+        for mesh in &frame.meshes {
+            let id = self.register_mesh(mesh);
+            // mesh vertices are already in world space
+            self.push_instance(id, Instance::default());
+        }
+    }
+
+    /// Uploads the vertices and indices of `mesh` with the next
+    /// [`update`](Self::update), to be drawn after the meshes registered
+    /// before it. It isn't drawn until it has an instance.
+    pub fn register_mesh(&mut self, mesh: &Mesh) -> MeshId {
+        if mesh.vertices.len() > Index::MAX as usize + 1 {
+            self.widen_indices();
+        }
+
+        let base_vertex = self.vertices.len() as i32;
+        self.vertices.extend(mesh.vertices.iter().map(|v| Vertex {
+            position: v.position.into(),
+            color: v.color.into(),
+        }));
+        let start = self.index_count();
+        match self.index_format {
+            wgpu::IndexFormat::Uint16 => self
+                .indices
+                .extend(mesh.indices.iter().map(|&i| i as Index)),
+            wgpu::IndexFormat::Uint32 => self.wide_indices.extend(mesh.indices.iter().copied()),
+        }
+        self.meshes.push(MeshEntry {
+            indices: start..self.index_count(),
+            base_vertex,
+            blend_mode: mesh.blend_mode,
+            instances: vec![],
+        });
+        MeshId(self.meshes.len() - 1)
+    }
+    /// Draws `mesh` once more, in front of its earlier instances.
+    pub fn push_instance(&mut self, mesh: MeshId, instance: Instance) {
+        self.meshes[mesh.0].instances.push(instance.into());
+    }
+    /// Removes all instances, keeping the meshes registered.
+    pub fn clear_instances(&mut self) {
+        for mesh in &mut self.meshes {
+            mesh.instances.clear();
+        }
+    }
+    /// Removes all meshes, after which earlier [`MeshId`]s are invalid.
+    pub fn clear_meshes(&mut self) {
+        self.vertices.clear();
+        self.indices.clear();
+        self.wide_indices.clear();
+        self.index_format = wgpu::IndexFormat::Uint16;
+        self.meshes.clear();
+    }
+
+    /// Moves the indices registered so far over to 32 bits. They are relative to
+    /// their meshes, so their values stay the same.
+    fn widen_indices(&mut self) {
+        if self.index_format == wgpu::IndexFormat::Uint16 {
+            self.wide_indices
+                .extend(self.indices.data.iter().map(|&i| i as WideIndex));
+            self.indices.clear();
+            self.index_format = wgpu::IndexFormat::Uint32;
+        }
+    }
+    fn index_count(&self) -> u32 {
+        match self.index_format {
+            wgpu::IndexFormat::Uint16 => self.indices.len() as u32,
+            wgpu::IndexFormat::Uint32 => self.wide_indices.len() as u32,
+        }
+    }
+    /// The index buffer the current frame uses, to bind with its format.
+    pub fn index_buffer(&self) -> wgpu::BufferSlice<'_> {
+        match self.index_format {
+            wgpu::IndexFormat::Uint16 => self.indices.slice(..),
+            wgpu::IndexFormat::Uint32 => self.wide_indices.slice(..),
         }
     }
     pub fn update(&mut self, renderer: &Renderer) {
+        self.instances.clear();
+        self.draws.clear();
+        for mesh in &self.meshes {
+            if mesh.instances.is_empty() {
+                continue;
+            }
+            let start = self.instances.len() as u32;
+            self.instances.extend(mesh.instances.iter().copied());
+            self.draws.push(Draw {
+                indices: mesh.indices.clone(),
+                base_vertex: mesh.base_vertex,
+                instances: start..self.instances.len() as u32,
+                blend_mode: mesh.blend_mode,
+            });
+        }
+
         self.camera.update(renderer);
         self.vertices.update(renderer);
-        self.indices.update(renderer);
+        match self.index_format {
+            wgpu::IndexFormat::Uint16 => self.indices.update(renderer),
+            wgpu::IndexFormat::Uint32 => self.wide_indices.update(renderer),
+        }
         self.instances.update(renderer);
     }
 }
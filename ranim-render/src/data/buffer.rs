@@ -39,9 +39,15 @@ impl<T: Pod> DynamicBuffer<T> {
     pub fn len(&self) -> usize {
         self.data.len()
     }
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
     pub fn push(&mut self, t: T) {
         self.data.push(t)
     }
+    pub fn clear(&mut self) {
+        self.data.clear()
+    }
 
     // Buffer operations
     pub fn size(&self) -> usize {
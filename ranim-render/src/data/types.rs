@@ -8,20 +8,23 @@ macro_rules! vertex_attr_array {
     }};
 }
 
+/// Index into the vertices of one mesh, enough for most of them.
 pub type Index = u16;
+/// Index used for the whole frame once a mesh has more vertices than [`Index`] can address.
+pub type WideIndex = u32;
 
 #[repr(C)]
 #[derive(Copy, Clone, Debug, Pod, Zeroable)]
 pub struct Vertex {
     pub position: [f32; 3],
-    pub color: [f32; 3],
+    pub color: [f32; 4],
 }
 impl Vertex {
     pub fn desc<'a>() -> wgpu::VertexBufferLayout<'a> {
         wgpu::VertexBufferLayout {
             array_stride: std::mem::size_of::<Self>() as wgpu::BufferAddress,
             step_mode: wgpu::VertexStepMode::Vertex,
-            attributes: vertex_attr_array![0 => Float32x3, 1 => Float32x3],
+            attributes: vertex_attr_array![0 => Float32x3, 1 => Float32x4],
         }
     }
 }
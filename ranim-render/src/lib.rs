@@ -1,15 +1,22 @@
 #![feature(array_chunks)]
 #![deny(rust_2018_idioms)]
 
+use std::num::NonZeroU32;
+
 use args::Args;
+use codec::Codec;
 use color_eyre::Result;
-use data::{types::{Vertex, InstanceRaw}, RenderData};
-use util::Size;
+use data::{types::{Vertex, InstanceRaw}, Draw, RenderData};
+use image::RgbaImage;
+use ranim::color::BlendMode;
+use util::{compute_work_group_count, Size, PIXEL_STRIDE};
 use winit::window::Window;
 
 pub mod args;
 pub mod camera;
+pub mod codec;
 pub mod data;
+pub mod output;
 pub mod util;
 pub mod video;
 
@@ -19,28 +26,53 @@ pub enum Error {
     NoAdapterFound,
     #[error(transparent)]
     RequestDeviceError(#[from] wgpu::RequestDeviceError),
+    #[error("No {codec} encoder in this FFmpeg build. Available encoders: {available}.")]
+    NoEncoderFound { codec: Codec, available: String },
+    #[error("No {codec} encoder in this FFmpeg build can encode in two passes.")]
+    NoTwoPassEncoder { codec: Codec },
+    #[error("A .{container} file can't hold {codec} video.")]
+    UnsupportedContainer { codec: Codec, container: String },
+}
+
+/// How the edges in rendered frames are smoothed.
+#[derive(Clone, Copy, Debug)]
+pub struct Antialiasing {
+    /// Samples per pixel of multisample antialiasing, or 1 for none.
+    pub msaa: u32,
+    /// Frames are rendered at this multiple of the output size, then filtered down to it.
+    pub supersample: u32,
+}
+impl Default for Antialiasing {
+    fn default() -> Self {
+        Self {
+            msaa: 4,
+            supersample: 1,
+        }
+    }
 }
 
 pub struct Renderer {
     pub(crate) device: wgpu::Device,
     pub(crate) queue: wgpu::Queue,
     pub(crate) size: Size,
+    pub(crate) antialiasing: Antialiasing,
 }
 impl Renderer {
     pub async fn new(args: &Args) -> Result<Self, Error> {
         let instance = wgpu::Instance::new(wgpu::Backends::all());
-        Self::new_inner(instance, None, args.quality.size()).await
+        Self::new_inner(instance, None, args.quality.size(), args.antialiasing()).await
     }
     pub async fn from_window(window: &Window) -> Result<Self, Error> {
         let instance = wgpu::Instance::new(wgpu::Backends::all());
         let size = window.inner_size();
         let surface = unsafe { instance.create_surface(window) };
-        Self::new_inner(instance, Some(surface), size.into()).await
+        Self::new_inner(instance, Some(surface), size.into(), Antialiasing::default()).await
     }
     async fn new_inner(
         instance: wgpu::Instance,
         surface: Option<wgpu::Surface>,
         size: Size,
+        antialiasing: Antialiasing,
     ) -> Result<Self, Error> {
         let adapter = instance
             .request_adapter(&wgpu::RequestAdapterOptions {
@@ -54,57 +86,148 @@ impl Renderer {
         let (device, queue) = adapter
             .request_device(&wgpu::DeviceDescriptor::default(), None)
             .await?;
-        Ok(Self { device, queue, size })
+        Ok(Self {
+            device,
+            queue,
+            size,
+            antialiasing,
+        })
+    }
+
+    /// The size frames are drawn at before supersampling is filtered down.
+    pub fn render_size(&self) -> Size {
+        let factor = self.antialiasing.supersample;
+        Size::new(self.size.width * factor, self.size.height * factor)
     }
 }
 
 
+/// Format of the stencil buffer that keeps overlapping triangles of one mesh from blending twice.
+const STENCIL_FORMAT: wgpu::TextureFormat = wgpu::TextureFormat::Depth24PlusStencil8;
+/// Meshes drawn between two clears of the stencil buffer, one per stencil value.
+const MESHES_PER_STENCIL_CLEAR: usize = 255;
+
 pub struct RenderPass {
-    pipeline: wgpu::RenderPipeline,
+    /// Two pipelines per [`BlendMode`], in the order they are declared: one
+    /// for meshes drawn once, which blend each pixel once, and one for
+    /// instanced meshes, whose instances blend over each other.
+    pipelines: Vec<[wgpu::RenderPipeline; 2]>,
+    stencil: TextureAndView,
+    /// Target with several samples per pixel, resolved when done, with MSAA.
+    multisampled: Option<TextureAndView>,
+    /// Target at the render size, filtered down to the output, with supersampling.
+    supersampled: Option<(TextureAndView, DownsamplePass)>,
 }
 impl RenderPass {
-    pub fn new(renderer: &Renderer, data: &RenderData) -> Self {
+    pub fn new(renderer: &Renderer, data: &RenderData, rgb: &RgbTexture) -> Self {
+        let samples = renderer.antialiasing.msaa;
+        let size = renderer.render_size();
         let layout = renderer.device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
             label: Some("Render Pipeline Layout"),
             bind_group_layouts: &[&data.camera.bind_group_layout],
             push_constant_ranges: &[],
         });
         let shader = renderer.device.create_shader_module(&wgpu::include_wgsl!("shaders/shader.wgsl"));
-        let pipeline = renderer.device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
-            label: Some("Render Pipeline"),
-            layout: Some(&layout),
-            vertex: wgpu::VertexState {
-                module: &shader,
-                entry_point: "vertex",
-                buffers: &[Vertex::desc(), InstanceRaw::desc()],
-            },
-            fragment: Some(wgpu::FragmentState {
-                module: &shader,
-                entry_point: "fragment",
-                targets: &[wgpu::ColorTargetState {
-                    format: wgpu::TextureFormat::Rgba8Unorm,
-                    blend: Some(wgpu::BlendState::REPLACE),
-                    write_mask: wgpu::ColorWrites::ALL,
-                }],
-            }),
-            primitive: wgpu::PrimitiveState {
-                topology: wgpu::PrimitiveTopology::TriangleStrip,
-                strip_index_format: None,
-                front_face: wgpu::FrontFace::Ccw,
-                cull_mode: Some(wgpu::Face::Back),
-                polygon_mode: wgpu::PolygonMode::Fill,
-                unclipped_depth: false,
-                conservative: false,
-            },
-            depth_stencil: None,
-            multisample: wgpu::MultisampleState {
-                count: 1,
-                mask: !0,
-                alpha_to_coverage_enabled: false,
-            },
-            multiview: None,
+        // a mesh only covers pixels it hasn't covered yet, which were last
+        // drawn with a different stencil value
+        let once = wgpu::StencilFaceState {
+            compare: wgpu::CompareFunction::NotEqual,
+            fail_op: wgpu::StencilOperation::Keep,
+            depth_fail_op: wgpu::StencilOperation::Keep,
+            pass_op: wgpu::StencilOperation::Replace,
+        };
+        let pipeline = |mode: BlendMode, stencil_face: wgpu::StencilFaceState| {
+            renderer.device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+                label: Some("Render Pipeline"),
+                layout: Some(&layout),
+                vertex: wgpu::VertexState {
+                    module: &shader,
+                    entry_point: "vertex",
+                    buffers: &[Vertex::desc(), InstanceRaw::desc()],
+                },
+                fragment: Some(wgpu::FragmentState {
+                    module: &shader,
+                    entry_point: "fragment",
+                    targets: &[wgpu::ColorTargetState {
+                        format: wgpu::TextureFormat::Rgba8Unorm,
+                        blend: Some(blend_state(mode)),
+                        write_mask: wgpu::ColorWrites::ALL,
+                    }],
+                }),
+                primitive: wgpu::PrimitiveState {
+                    topology: wgpu::PrimitiveTopology::TriangleList,
+                    strip_index_format: None,
+                    front_face: wgpu::FrontFace::Ccw,
+                    // mobject outlines can be wound either way
+                    cull_mode: None,
+                    polygon_mode: wgpu::PolygonMode::Fill,
+                    unclipped_depth: false,
+                    conservative: false,
+                },
+                depth_stencil: Some(wgpu::DepthStencilState {
+                    format: STENCIL_FORMAT,
+                    depth_write_enabled: false,
+                    depth_compare: wgpu::CompareFunction::Always,
+                    stencil: wgpu::StencilState {
+                        front: stencil_face,
+                        back: stencil_face,
+                        read_mask: 0xff,
+                        write_mask: 0xff,
+                    },
+                    bias: wgpu::DepthBiasState::default(),
+                }),
+                multisample: wgpu::MultisampleState {
+                    count: samples,
+                    mask: !0,
+                    alpha_to_coverage_enabled: false,
+                },
+                multiview: None,
+            })
+        };
+        let pipelines = BlendMode::ALL
+            .iter()
+            .map(|&mode| [pipeline(mode, once), pipeline(mode, wgpu::StencilFaceState::IGNORE)])
+            .collect();
+
+        let stencil = TextureAndView::new(&renderer.device, &wgpu::TextureDescriptor {
+            size: size.extent(),
+            mip_level_count: 1,
+            sample_count: samples,
+            dimension: wgpu::TextureDimension::D2,
+            format: STENCIL_FORMAT,
+            usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
+            label: Some("Stencil texture"),
+        });
+        let multisampled = (samples > 1).then(|| {
+            TextureAndView::new(&renderer.device, &wgpu::TextureDescriptor {
+                size: size.extent(),
+                mip_level_count: 1,
+                sample_count: samples,
+                dimension: wgpu::TextureDimension::D2,
+                format: wgpu::TextureFormat::Rgba8Unorm,
+                usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
+                label: Some("Multisampled texture"),
+            })
+        });
+        let supersampled = (renderer.antialiasing.supersample > 1).then(|| {
+            let texture = TextureAndView::new(&renderer.device, &wgpu::TextureDescriptor {
+                size: size.extent(),
+                mip_level_count: 1,
+                sample_count: 1,
+                dimension: wgpu::TextureDimension::D2,
+                format: wgpu::TextureFormat::Rgba8Unorm,
+                usage: wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::TEXTURE_BINDING,
+                label: Some("Supersampled texture"),
+            });
+            let downsample = DownsamplePass::new(renderer, &texture, rgb);
+            (texture, downsample)
         });
-        Self { pipeline }
+        Self {
+            pipelines,
+            stencil,
+            multisampled,
+            supersampled,
+        }
     }
     pub fn execute(
         &mut self,
@@ -112,35 +235,146 @@ impl RenderPass {
         rgb: &RgbTexture,
         data: &RenderData,
     ) {
-        let mut pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
-            label: Some("Render Pass"),
-            color_attachments: &[wgpu::RenderPassColorAttachment {
-                view: &rgb.tv.view,
-                resolve_target: None,
-                ops: wgpu::Operations {
-                    load: wgpu::LoadOp::Clear(wgpu::Color {
-                        r: 0.9,
-                        g: 0.7,
-                        b: 0.4,
-                        a: 1.0,
-                    }),
-                    store: true,
-                },
-            }],
-            depth_stencil_attachment: None,
+        let background = data.background;
+        let mut load = wgpu::LoadOp::Clear(wgpu::Color {
+            r: (background.r * background.a) as f64,
+            g: (background.g * background.a) as f64,
+            b: (background.b * background.a) as f64,
+            a: background.a as f64,
         });
+        // at least one pass, to clear the background
+        let chunks: Vec<&[Draw]> = match data.draws.is_empty() {
+            true => vec![&[]],
+            false => data.draws.chunks(MESHES_PER_STENCIL_CLEAR).collect(),
+        };
+        // drawn into the supersampled texture if any, through the multisampled one if any
+        let single_sampled = match &self.supersampled {
+            Some((texture, _)) => &texture.view,
+            None => &rgb.tv.view,
+        };
+        let (view, resolve_target) = match &self.multisampled {
+            Some(texture) => (&texture.view, Some(single_sampled)),
+            None => (single_sampled, None),
+        };
+        let last = chunks.len() - 1;
+        for (i, draws) in chunks.into_iter().enumerate() {
+            let mut pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                label: Some("Render Pass"),
+                color_attachments: &[wgpu::RenderPassColorAttachment {
+                    view,
+                    resolve_target: resolve_target.filter(|_| i == last),
+                    ops: wgpu::Operations { load, store: true },
+                }],
+                depth_stencil_attachment: Some(wgpu::RenderPassDepthStencilAttachment {
+                    view: &self.stencil.view,
+                    depth_ops: None,
+                    stencil_ops: Some(wgpu::Operations {
+                        load: wgpu::LoadOp::Clear(0),
+                        store: false,
+                    }),
+                }),
+            });
+            load = wgpu::LoadOp::Load;
+
+            pass.set_bind_group(0, &data.camera.bind_group, &[]);
+            pass.set_vertex_buffer(0, data.vertices.slice(..));
+            pass.set_vertex_buffer(1, data.instances.slice(..));
+            pass.set_index_buffer(data.index_buffer(), data.index_format);
 
+            for (i, draw) in draws.iter().enumerate() {
+                let instanced = draw.instances.len() > 1;
+                pass.set_pipeline(&self.pipelines[draw.blend_mode as usize][instanced as usize]);
+                pass.set_stencil_reference(i as u32 + 1);
+                pass.draw_indexed(
+                    draw.indices.clone(),
+                    draw.base_vertex,
+                    draw.instances.clone(),
+                );
+            }
+        }
+
+        if let Some((_, downsample)) = &self.supersampled {
+            downsample.execute(encoder);
+        }
+    }
+}
+
+/// Filters a supersampled frame down to the output size.
+struct DownsamplePass {
+    pipeline: wgpu::ComputePipeline,
+    bind_group: wgpu::BindGroup,
+    dispatch_x: u32,
+    dispatch_y: u32,
+}
+impl DownsamplePass {
+    fn new(renderer: &Renderer, src: &TextureAndView, dst: &RgbTexture) -> Self {
+        let shader = renderer
+            .device
+            .create_shader_module(&wgpu::include_wgsl!("shaders/downsample.wgsl"));
+        let pipeline = renderer
+            .device
+            .create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
+                label: Some("Downsample pipeline"),
+                layout: None,
+                module: &shader,
+                entry_point: "downsample_main",
+            });
+        let (dispatch_x, dispatch_y) = compute_work_group_count(renderer.size, (16, 16));
+
+        let bind_group = renderer
+            .device
+            .create_bind_group(&wgpu::BindGroupDescriptor {
+                label: Some("Downsample bind group"),
+                layout: &pipeline.get_bind_group_layout(0),
+                entries: &[
+                    wgpu::BindGroupEntry {
+                        binding: 0,
+                        resource: wgpu::BindingResource::TextureView(&src.view),
+                    },
+                    wgpu::BindGroupEntry {
+                        binding: 1,
+                        resource: wgpu::BindingResource::TextureView(&dst.tv.view),
+                    },
+                ],
+            });
+
+        Self {
+            pipeline,
+            bind_group,
+            dispatch_x,
+            dispatch_y,
+        }
+    }
+    fn execute(&self, encoder: &mut wgpu::CommandEncoder) {
+        let mut pass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
+            label: Some("Downsample pass"),
+        });
         pass.set_pipeline(&self.pipeline);
-        pass.set_bind_group(0, &data.camera.bind_group, &[]);
-        pass.set_vertex_buffer(0, data.vertices.slice(..));
-        pass.set_vertex_buffer(1, data.instances.slice(..));
-        pass.set_index_buffer(data.indices.slice(..), wgpu::IndexFormat::Uint16);
-
-        pass.draw_indexed(
-            0..data.indices.len() as u32,
-            0,
-            0..data.instances.len() as u32,
-        );
+        pass.set_bind_group(0, &self.bind_group, &[]);
+        pass.dispatch(self.dispatch_x, self.dispatch_y, 1);
+    }
+}
+
+/// Blending of premultiplied colors for `mode`. Alpha is always blended as in [`BlendMode::Normal`], so
+/// coverage adds up the same way for every mode.
+fn blend_state(mode: BlendMode) -> wgpu::BlendState {
+    use wgpu::BlendFactor::*;
+
+    let (src_factor, dst_factor) = match mode {
+        BlendMode::Normal => (One, OneMinusSrcAlpha),
+        BlendMode::Add => (One, One),
+        // src * dst + dst * (1 - src_alpha)
+        BlendMode::Multiply => (Dst, OneMinusSrcAlpha),
+        // src + dst * (1 - src)
+        BlendMode::Screen => (One, OneMinusSrc),
+    };
+    wgpu::BlendState {
+        color: wgpu::BlendComponent {
+            src_factor,
+            dst_factor,
+            operation: wgpu::BlendOperation::Add,
+        },
+        alpha: wgpu::BlendComponent::OVER,
     }
 }
 
@@ -169,10 +403,88 @@ impl RgbTexture {
             format: wgpu::TextureFormat::Rgba8Unorm,
             usage: wgpu::TextureUsages::RENDER_ATTACHMENT
                 | wgpu::TextureUsages::TEXTURE_BINDING
+                | wgpu::TextureUsages::STORAGE_BINDING
                 | wgpu::TextureUsages::COPY_SRC,
             label: Some("RGB texture"),
         };
         let tv = TextureAndView::new(&renderer.device, &desc);
         Self { tv }
     }
-}
\ No newline at end of file
+}
+
+/// A copy of the [`RgbTexture`] the CPU can read, for outputs that take whole images.
+pub struct RgbBuffer {
+    buf: wgpu::Buffer,
+    size: Size,
+}
+impl RgbBuffer {
+    pub fn new(renderer: &Renderer) -> Self {
+        let desc = wgpu::BufferDescriptor {
+            size: renderer.size.buffer_size() as wgpu::BufferAddress,
+            usage: wgpu::BufferUsages::COPY_DST | wgpu::BufferUsages::MAP_READ,
+            label: Some("RGB buffer"),
+            mapped_at_creation: false,
+        };
+        let buf = renderer.device.create_buffer(&desc);
+        Self {
+            buf,
+            size: renderer.size,
+        }
+    }
+    pub fn copy_from(&self, encoder: &mut wgpu::CommandEncoder, rgb: &RgbTexture) {
+        encoder.copy_texture_to_buffer(
+            wgpu::ImageCopyTexture {
+                aspect: wgpu::TextureAspect::All,
+                texture: &rgb.tv.texture,
+                mip_level: 0,
+                origin: wgpu::Origin3d::ZERO,
+            },
+            wgpu::ImageCopyBuffer {
+                buffer: &self.buf,
+                layout: wgpu::ImageDataLayout {
+                    offset: 0,
+                    bytes_per_row: NonZeroU32::new(self.size.bytes_per_row),
+                    rows_per_image: NonZeroU32::new(self.size.height),
+                },
+            },
+            self.size.extent(),
+        );
+    }
+    /// The copied frame with straight alpha, once the copy has been submitted.
+    pub async fn read(&self, device: &wgpu::Device) -> RgbaImage {
+        let buf = self.buf.slice(..);
+
+        // NOTE: as for the YUV buffer, poll the device before awaiting the mapping.
+        let mapping = buf.map_async(wgpu::MapMode::Read);
+        device.poll(wgpu::Maintain::Wait);
+        mapping
+            .await
+            .expect("Could not asynchronously map buffer to host");
+
+        let width = self.size.width as usize;
+        let mut image = RgbaImage::new(self.size.width, self.size.height);
+        {
+            let view = buf.get_mapped_range();
+            let rows = view.chunks(self.size.bytes_per_row as usize);
+            for (src, dst) in rows.zip(image.chunks_mut(width * PIXEL_STRIDE)) {
+                for (src, dst) in src[..width * PIXEL_STRIDE]
+                    .chunks_exact(PIXEL_STRIDE)
+                    .zip(dst.chunks_exact_mut(PIXEL_STRIDE))
+                {
+                    dst.copy_from_slice(&unpremultiply([src[0], src[1], src[2], src[3]]));
+                }
+            }
+        }
+        self.buf.unmap();
+        image
+    }
+}
+
+/// Undoes the multiplication of the color by alpha that blending is done with.
+fn unpremultiply([r, g, b, a]: [u8; 4]) -> [u8; 4] {
+    if a == 0 {
+        return [0; 4];
+    }
+    let channel = |c: u8| ((c as u32 * 255 + a as u32 / 2) / a as u32).min(255) as u8;
+    [channel(r), channel(g), channel(b), a]
+}
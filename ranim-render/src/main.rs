@@ -1,7 +1,12 @@
 use clap::Parser;
 use color_eyre::Result;
-use glam::{vec4, vec3, Vec3};
-use ranim_render::{args::Args, data::types::{Vertex, Instance}, video::VideoRenderer};
+use glam::vec3;
+use ranim::{
+    color::palette::{BLUE, GOLD, GREEN, MAROON, PURPLE, RED, TEAL, WHITE, YELLOW},
+    mobj::layout::{DEFAULT_EDGE_BUFF, SMALL_BUFF},
+    prelude::*,
+};
+use ranim_render::{args::Args, video::VideoRenderer};
 
 fn main() -> Result<()> {
     pollster::block_on(run())
@@ -18,46 +23,50 @@ async fn run() -> Result<()> {
 }
 
 async fn video(args: Args) -> Result<()> {
-    use std::f32::consts::*;
-
+    let config = args.scene_config();
+    let mut scene = Scene::new(config);
     let mut renderer = VideoRenderer::new(args).await?;
 
-    renderer.data.vertices.push(Vertex {
-        position: [0.0, 0.0, 0.0],
-        color: [1.0, 1.0, 1.0],
-    });
-
-    let n = 40;
-    for i in 1..=n {
-        let rad = (i as f32 / n as f32) * TAU;
-        renderer.data.vertices.extend([Vertex {
-            position: [-rad.cos(), rad.sin(), 0.0],
-            color: [1.0, 1.0, 1.0],
-        }]);
-        renderer.data.indices.extend([i, 0]);
-    }
-    renderer.data.indices.push(1);
+    let mut circle = Circle::new(1.0);
+    circle.fill_color = WHITE;
+    scene.add(circle);
+    scene.wait(1.0);
 
-    for _ in 0..60 {
-        let x = 5.0 * rand::random::<f32>() - 2.5;
-        let y = 5.0 * rand::random::<f32>() - 2.5;
-        let s = 0.9 * rand::random::<f32>() + 0.1;
-        let color: Vec3 = rand::random::<[f32; 3]>().into();
+    let mut previous = None;
+    for color in [BLUE, TEAL, GREEN, YELLOW, GOLD, RED, MAROON, PURPLE] {
+        let size = rand::random::<f32>() + 0.2;
 
-        renderer.data.instances.push(
-            Instance {
-                position: vec3(x, y, 0.0),
-                scale: vec3(s, s, 0.0),
-                color: vec4(color.x, color.y, color.z, 1.0),
-                ..Instance::default()
+        let mut square = Polygon::rectangle(size, size);
+        square.set_color(color);
+        match previous {
+            Some(previous) => {
+                square.next_to(previous, RIGHT, SMALL_BUFF);
+                square.align_to(previous, UP);
             }
-            .into(),
-        );
-        for _ in 0..2 {
-            renderer.update();
-            renderer.render().await?;
+            None => square.to_corner(UL, DEFAULT_EDGE_BUFF, &config),
         }
+        previous = square.bounding_box();
+        scene.add(square);
+        scene.wait(0.25);
     }
+
+    let zigzag = scene.insert(VMobject::from_corners(
+        &(0..=8)
+            .map(|i| vec3(i as f32 - 4.0, if i % 2 == 0 { -3.0 } else { -2.0 }, 0.0))
+            .collect::<Vec<_>>(),
+    ));
+    let star = scene.insert(Polygon::star(5, 1.5, 0.6).to_vmobject());
+    scene.play(LaggedStart::with_lag_ratio(
+        vec![Box::new(Create::new(zigzag)), Box::new(Create::new(star))],
+        0.5,
+    ));
+    scene.wait(0.5);
+
+    let hexagon = scene.insert(Polygon::regular(6, 1.5).to_vmobject());
+    scene.play(ReplacementTransform::new(star, hexagon));
+    scene.wait(1.0);
+
+    renderer.render_scene(&scene).await?;
     renderer.conclude()?;
 
     Ok(())
@@ -0,0 +1,470 @@
+use std::{
+    borrow::Cow,
+    ffi::CString,
+    fs::{self, File},
+    io::BufWriter,
+    path::{Path, PathBuf},
+};
+
+use color_eyre::{eyre::eyre, Result};
+use color_quant::NeuQuant;
+use cstr::cstr;
+use enum_dispatch::enum_dispatch;
+use image::RgbaImage;
+use rsmpeg::{
+    avcodec::{AVCodec, AVCodecContext},
+    avformat::AVFormatContextOutput,
+    avutil::{ra, AVFrame},
+    error::RsmpegError,
+    ffi,
+    swscale::SwsContext,
+};
+
+use crate::{
+    args::{Args, GifPalette},
+    util::{Size, PIXEL_STRIDE},
+};
+
+/// Takes frames read back from the RGB texture, with straight alpha, and writes them to files.
+#[enum_dispatch]
+pub trait FrameWriter {
+    /// Writes the next frame, or holds on to it until [`conclude`](Self::conclude).
+    fn write(&mut self, frame: &RgbaImage) -> Result<()>;
+    /// Finishes the output once the last frame has been written.
+    fn conclude(&mut self) -> Result<()>;
+}
+
+/// The image outputs, chosen by the extension of the output file.
+#[enum_dispatch(FrameWriter)]
+pub enum ImageWriter {
+    PngWriter,
+    GifWriter,
+    ApngWriter,
+    WebpWriter,
+}
+impl ImageWriter {
+    /// The writer for `args`: a single PNG for `--single-frame`, otherwise one picked by the
+    /// extension of the output file. `None` means the file is a video.
+    pub fn new(args: &Args) -> Result<Option<Self>> {
+        let path = &args.output_file;
+        let size = args.quality.size();
+        let frame_rate = args.quality.frame_rate();
+
+        if args.single_frame {
+            return Ok(Some(PngWriter::single(path)?.into()));
+        }
+        let extension = path
+            .extension()
+            .map(|ext| ext.to_string_lossy().to_ascii_lowercase());
+        let writer = match extension.as_deref() {
+            Some("png") => PngWriter::sequence(path)?.into(),
+            Some("apng") => ApngWriter::new(path, size, frame_rate)?.into(),
+            Some("gif") => {
+                GifWriter::new(path, size, frame_rate, args.gif_palette, args.dither)?.into()
+            }
+            Some("webp") => WebpWriter::new(path, size, frame_rate)?.into(),
+            _ => return Ok(None),
+        };
+        Ok(Some(writer))
+    }
+}
+
+fn create_parent_dir(path: &Path) -> Result<()> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    Ok(())
+}
+
+/// Writes frames as PNG images, either all of them as a numbered sequence or only one.
+pub struct PngWriter {
+    path: PathBuf,
+    numbered: bool,
+    frame_cnt: usize,
+}
+impl PngWriter {
+    /// Writes each frame to its own file, numbered after the name of `path`: `output.png` becomes
+    /// `output_0000.png`, `output_0001.png` and so on.
+    pub fn sequence(path: &Path) -> Result<Self> {
+        Self::new(path, true)
+    }
+    /// Writes frames to `path`, each one replacing the one before.
+    pub fn single(path: &Path) -> Result<Self> {
+        Self::new(path, false)
+    }
+    fn new(path: &Path, numbered: bool) -> Result<Self> {
+        create_parent_dir(path)?;
+        Ok(Self {
+            path: path.with_extension("png"),
+            numbered,
+            frame_cnt: 0,
+        })
+    }
+}
+impl FrameWriter for PngWriter {
+    fn write(&mut self, frame: &RgbaImage) -> Result<()> {
+        let path = if self.numbered {
+            let stem = self.path.file_stem().unwrap_or_default().to_string_lossy();
+            self.path
+                .with_file_name(format!("{stem}_{:04}.png", self.frame_cnt))
+        } else {
+            self.path.clone()
+        };
+        frame.save(path)?;
+        self.frame_cnt += 1;
+        Ok(())
+    }
+    fn conclude(&mut self) -> Result<()> {
+        Ok(())
+    }
+}
+
+/// Writes frames as a looping animated GIF.
+///
+/// GIF has 256 colors per frame and no partial transparency: pixels at least half transparent
+/// become fully transparent, the rest opaque. With a global palette, frames are kept in memory
+/// until the palette can be built from all of them.
+pub struct GifWriter {
+    path: PathBuf,
+    size: Size,
+    frame_rate: u32,
+    palette: GifPalette,
+    dither: bool,
+    encoder: Option<gif::Encoder<BufWriter<File>>>,
+    frames: Vec<RgbaImage>,
+    frame_cnt: u32,
+}
+impl GifWriter {
+    pub fn new(
+        path: &Path,
+        size: Size,
+        frame_rate: u32,
+        palette: GifPalette,
+        dither: bool,
+    ) -> Result<Self> {
+        create_parent_dir(path)?;
+        if frame_rate > 50 {
+            log::warn!(
+                "most GIF players slow down frames shorter than 2/100 s, so {frame_rate} fps may play back slower"
+            );
+        }
+        Ok(Self {
+            path: path.to_owned(),
+            size,
+            frame_rate,
+            palette,
+            dither,
+            encoder: None,
+            frames: vec![],
+            frame_cnt: 0,
+        })
+    }
+
+    fn open(&self, global_palette: &[u8]) -> Result<gif::Encoder<BufWriter<File>>> {
+        let file = BufWriter::new(File::create(&self.path)?);
+        let mut encoder = gif::Encoder::new(
+            file,
+            self.size.width as u16,
+            self.size.height as u16,
+            global_palette,
+        )?;
+        encoder.set_repeat(gif::Repeat::Infinite)?;
+        Ok(encoder)
+    }
+
+    /// Builds the GIF frame of `image` from `quantizer`, with its palette unless that is global.
+    fn frame(&mut self, image: &RgbaImage, quantizer: &Quantizer, local: bool) -> gif::Frame<'static> {
+        // delays are in hundredths of a second; round the end of each frame so they don't drift
+        let end = |n: u32| (n * 100 + self.frame_rate / 2) / self.frame_rate;
+        let delay = end(self.frame_cnt + 1) - end(self.frame_cnt);
+        self.frame_cnt += 1;
+
+        gif::Frame {
+            width: self.size.width as u16,
+            height: self.size.height as u16,
+            buffer: Cow::Owned(quantizer.index(image, self.dither)),
+            palette: local.then(|| quantizer.palette.clone()),
+            transparent: Some(Quantizer::TRANSPARENT),
+            delay: delay as u16,
+            // transparent pixels show the background, not the frame before
+            dispose: gif::DisposalMethod::Background,
+            ..Default::default()
+        }
+    }
+}
+impl FrameWriter for GifWriter {
+    fn write(&mut self, frame: &RgbaImage) -> Result<()> {
+        match self.palette {
+            GifPalette::PerFrame => {
+                if self.encoder.is_none() {
+                    self.encoder = Some(self.open(&[])?);
+                }
+                let quantizer = Quantizer::new([frame]);
+                let frame = self.frame(frame, &quantizer, true);
+                self.encoder.as_mut().unwrap().write_frame(&frame)?;
+            }
+            GifPalette::Global => self.frames.push(frame.clone()),
+        }
+        Ok(())
+    }
+    fn conclude(&mut self) -> Result<()> {
+        if self.palette == GifPalette::Global && !self.frames.is_empty() {
+            let frames = std::mem::take(&mut self.frames);
+            let quantizer = Quantizer::new(&frames);
+            let mut encoder = self.open(&quantizer.palette)?;
+            for image in &frames {
+                encoder.write_frame(&self.frame(image, &quantizer, false))?;
+            }
+            self.encoder = Some(encoder);
+        }
+        // the trailer is written when the encoder is dropped
+        self.encoder = None;
+        Ok(())
+    }
+}
+
+/// A palette of up to 255 colors learned from some frames, plus a transparent entry.
+struct Quantizer {
+    quant: NeuQuant,
+    /// RGB triples, with [`TRANSPARENT`](Self::TRANSPARENT) last.
+    palette: Vec<u8>,
+}
+impl Quantizer {
+    const TRANSPARENT: u8 = 255;
+    /// Pixels are sampled from at most about this many to learn the palette.
+    const MAX_SAMPLES: usize = 1 << 22;
+
+    fn new<'a>(frames: impl IntoIterator<Item = &'a RgbaImage> + Clone) -> Self {
+        let total: usize = frames.clone().into_iter().map(|f| f.as_raw().len()).sum();
+        let step = (total / PIXEL_STRIDE / Self::MAX_SAMPLES).max(1);
+
+        let mut samples: Vec<u8> = frames
+            .into_iter()
+            .flat_map(|f| f.as_raw().chunks_exact(PIXEL_STRIDE).step_by(step))
+            .filter(|p| p[3] >= 128)
+            .flat_map(|p| [p[0], p[1], p[2], 255])
+            .collect();
+        if samples.is_empty() {
+            samples.extend([0, 0, 0, 255]);
+        }
+
+        let quant = NeuQuant::new(10, Self::TRANSPARENT as usize, &samples);
+        let mut palette = quant.color_map_rgb();
+        palette.extend([0, 0, 0]);
+        Self { quant, palette }
+    }
+
+    /// The palette index of each pixel of `image`, optionally Floyd-Steinberg dithered.
+    fn index(&self, image: &RgbaImage, dither: bool) -> Vec<u8> {
+        let width = image.width() as usize;
+        let mut indices = Vec::with_capacity(image.as_raw().len() / PIXEL_STRIDE);
+        // quantization error carried to the current and the next row
+        let mut errors = [vec![[0.0f32; 3]; width + 2], vec![[0.0f32; 3]; width + 2]];
+
+        for row in image.as_raw().chunks_exact(width * PIXEL_STRIDE) {
+            errors.swap(0, 1);
+            errors[1].iter_mut().for_each(|e| *e = [0.0; 3]);
+            for (x, p) in row.chunks_exact(PIXEL_STRIDE).enumerate() {
+                if p[3] < 128 {
+                    indices.push(Self::TRANSPARENT);
+                    continue;
+                }
+                let mut color = [p[0] as f32, p[1] as f32, p[2] as f32];
+                if dither {
+                    for (c, e) in color.iter_mut().zip(errors[0][x + 1]) {
+                        *c = (*c + e).clamp(0.0, 255.0);
+                    }
+                }
+                let pixel = color.map(|c| c.round() as u8);
+                let index = self.quant.index_of(&[pixel[0], pixel[1], pixel[2], 255]);
+                indices.push(index as u8);
+
+                if dither {
+                    let chosen = &self.palette[index * 3..index * 3 + 3];
+                    for i in 0..3 {
+                        let error = color[i] - chosen[i] as f32;
+                        errors[0][x + 2][i] += error * 7.0 / 16.0;
+                        errors[1][x][i] += error * 3.0 / 16.0;
+                        errors[1][x + 1][i] += error * 5.0 / 16.0;
+                        errors[1][x + 2][i] += error * 1.0 / 16.0;
+                    }
+                }
+            }
+        }
+        indices
+    }
+}
+
+/// Writes frames as a looping animated PNG, with full color and alpha.
+///
+/// The number of frames goes in the header, so frames are kept in memory until the last one.
+pub struct ApngWriter {
+    path: PathBuf,
+    size: Size,
+    frame_rate: u32,
+    frames: Vec<RgbaImage>,
+}
+impl ApngWriter {
+    pub fn new(path: &Path, size: Size, frame_rate: u32) -> Result<Self> {
+        create_parent_dir(path)?;
+        Ok(Self {
+            path: path.to_owned(),
+            size,
+            frame_rate,
+            frames: vec![],
+        })
+    }
+}
+impl FrameWriter for ApngWriter {
+    fn write(&mut self, frame: &RgbaImage) -> Result<()> {
+        self.frames.push(frame.clone());
+        Ok(())
+    }
+    fn conclude(&mut self) -> Result<()> {
+        if self.frames.is_empty() {
+            return Ok(());
+        }
+        let file = BufWriter::new(File::create(&self.path)?);
+        let mut encoder = png::Encoder::new(file, self.size.width, self.size.height);
+        encoder.set_color(png::ColorType::Rgba);
+        encoder.set_depth(png::BitDepth::Eight);
+        // zero plays means looping forever
+        encoder.set_animated(self.frames.len() as u32, 0)?;
+        encoder.set_frame_delay(1, self.frame_rate as u16)?;
+
+        let mut writer = encoder.write_header()?;
+        for frame in self.frames.drain(..) {
+            writer.write_image_data(&frame)?;
+        }
+        writer.finish()?;
+        Ok(())
+    }
+}
+
+/// Writes frames as a looping animated WebP, with alpha, through FFmpeg's libwebp encoder.
+pub struct WebpWriter {
+    encode_ctx: AVCodecContext,
+    output_ctx: AVFormatContextOutput,
+    sws_ctx: SwsContext,
+    rgba_frame: AVFrame,
+    frame: AVFrame,
+    frame_cnt: i64,
+}
+impl WebpWriter {
+    pub fn new(path: &Path, size: Size, frame_rate: u32) -> Result<Self> {
+        create_parent_dir(path)?;
+
+        let encode_ctx = {
+            let encoder = AVCodec::find_encoder_by_name(cstr!("libwebp_anim"))
+                .ok_or_else(|| eyre!("animated WebP needs an FFmpeg built with libwebp"))?;
+            let mut ctx = AVCodecContext::new(&encoder);
+            ctx.set_width(size.width as i32);
+            ctx.set_height(size.height as i32);
+            ctx.set_time_base(ra(1, frame_rate as i32));
+            ctx.set_framerate(ra(frame_rate as i32, 1));
+            ctx.set_pix_fmt(ffi::AVPixelFormat_AV_PIX_FMT_YUVA420P);
+            ctx.open(None)?;
+            ctx
+        };
+
+        let new_frame = |format| -> Result<AVFrame> {
+            let mut frame = AVFrame::new();
+            frame.set_format(format);
+            frame.set_width(encode_ctx.width);
+            frame.set_height(encode_ctx.height);
+            frame.alloc_buffer()?;
+            Ok(frame)
+        };
+        let rgba_frame = new_frame(ffi::AVPixelFormat_AV_PIX_FMT_RGBA)?;
+        let frame = new_frame(encode_ctx.pix_fmt)?;
+        let sws_ctx = SwsContext::get_context(
+            encode_ctx.width,
+            encode_ctx.height,
+            ffi::AVPixelFormat_AV_PIX_FMT_RGBA,
+            encode_ctx.width,
+            encode_ctx.height,
+            encode_ctx.pix_fmt,
+            ffi::SWS_BICUBIC,
+        )
+        .ok_or_else(|| eyre!("Failed to create RGBA to YUVA conversion"))?;
+
+        let output_ctx = {
+            let output_path = CString::new(path.to_string_lossy().as_ref()).unwrap();
+            let mut output_ctx = AVFormatContextOutput::create(&output_path, None)?;
+            {
+                let mut stream = output_ctx.new_stream();
+                stream.set_codecpar(encode_ctx.extract_codecpar());
+                stream.set_time_base(encode_ctx.time_base);
+            }
+            // the WebP muxer plays once by default; zero loops forever
+            unsafe {
+                ffi::av_opt_set_int(output_ctx.priv_data, cstr!("loop").as_ptr(), 0, 0);
+            }
+            output_ctx.dump(0, &output_path)?;
+            output_ctx.write_header()?;
+            output_ctx
+        };
+
+        Ok(Self {
+            encode_ctx,
+            output_ctx,
+            sws_ctx,
+            rgba_frame,
+            frame,
+            frame_cnt: 0,
+        })
+    }
+
+    fn write_packets(&mut self) -> Result<()> {
+        loop {
+            let mut packet = match self.encode_ctx.receive_packet() {
+                Ok(packet) => packet,
+                Err(RsmpegError::EncoderDrainError) | Err(RsmpegError::EncoderFlushedError) => {
+                    break
+                }
+                Err(e) => return Err(e.into()),
+            };
+            packet.rescale_ts(
+                self.encode_ctx.time_base,
+                self.output_ctx.streams().get(0).unwrap().time_base,
+            );
+            self.output_ctx.write_frame(&mut packet)?;
+        }
+        Ok(())
+    }
+}
+impl FrameWriter for WebpWriter {
+    fn write(&mut self, frame: &RgbaImage) -> Result<()> {
+        let row_len = frame.width() as usize * PIXEL_STRIDE;
+        let linesize = self.rgba_frame.linesize[0] as usize;
+        let dst = unsafe {
+            std::slice::from_raw_parts_mut(
+                self.rgba_frame.data[0],
+                linesize * frame.height() as usize,
+            )
+        };
+        for (src, dst) in frame.chunks_exact(row_len).zip(dst.chunks_mut(linesize)) {
+            dst[..row_len].copy_from_slice(src);
+        }
+        // the encoder may still hold on to the last frame
+        self.frame.make_writable()?;
+        self.sws_ctx.scale_frame(
+            &self.rgba_frame,
+            0,
+            self.encode_ctx.height,
+            &mut self.frame,
+        )?;
+
+        self.frame.set_pts(self.frame_cnt);
+        self.frame_cnt += 1;
+
+        self.encode_ctx.send_frame(Some(&self.frame))?;
+        self.write_packets()
+    }
+    fn conclude(&mut self) -> Result<()> {
+        self.encode_ctx.send_frame(None)?;
+        self.write_packets()?;
+        self.output_ctx.write_trailer()?;
+        Ok(())
+    }
+}
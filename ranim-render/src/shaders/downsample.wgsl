@@ -0,0 +1,52 @@
+[[group(0), binding(0)]] var input_texture : texture_2d<f32>;
+[[group(0), binding(1)]] var output_texture : texture_storage_2d<rgba8unorm, write>;
+
+// Mitchell-Netravali filter with B = C = 1/3, which is zero from a distance of 2 on.
+fn mitchell(x: f32) -> f32 {
+    let t = abs(x);
+    if (t < 1.0) {
+        return (7.0 * t * t * t - 12.0 * t * t + 16.0 / 3.0) / 6.0;
+    }
+    if (t < 2.0) {
+        return (-7.0 / 3.0 * t * t * t + 12.0 * t * t - 20.0 * t + 32.0 / 3.0) / 6.0;
+    }
+    return 0.0;
+}
+
+[[stage(compute), workgroup_size(16, 16)]]
+fn downsample_main(
+  [[builtin(global_invocation_id)]] global_id: vec3<u32>,
+) {
+    let output_size = textureDimensions(output_texture);
+    let coords = vec2<i32>(global_id.xy);
+    if(coords.x >= output_size.x || coords.y >= output_size.y) {
+        return;
+    }
+
+    let input_size = textureDimensions(input_texture);
+    let factor = input_size.x / output_size.x;
+    let scale = f32(factor);
+    // center of the output pixel, in input pixels
+    let center = (vec2<f32>(coords) + vec2<f32>(0.5, 0.5)) * scale;
+    let first = coords * factor - vec2<i32>(2 * factor, 2 * factor);
+    let last = coords * factor + vec2<i32>(3 * factor, 3 * factor);
+    let max_coords = input_size - vec2<i32>(1, 1);
+
+    var sum = vec4<f32>(0.0, 0.0, 0.0, 0.0);
+    var total: f32 = 0.0;
+    for (var y: i32 = first.y; y < last.y; y = y + 1) {
+        let wy = mitchell((f32(y) + 0.5 - center.y) / scale);
+        for (var x: i32 = first.x; x < last.x; x = x + 1) {
+            let weight = wy * mitchell((f32(x) + 0.5 - center.x) / scale);
+            let p = clamp(vec2<i32>(x, y), vec2<i32>(0, 0), max_coords);
+            sum = sum + weight * textureLoad(input_texture, p, 0);
+            total = total + weight;
+        }
+    }
+
+    // the negative lobes can overshoot; keep the color premultiplied and in range
+    let color = sum / total;
+    let alpha = clamp(color.a, 0.0, 1.0);
+    let rgb = clamp(color.rgb, vec3<f32>(0.0, 0.0, 0.0), vec3<f32>(alpha, alpha, alpha));
+    textureStore(output_texture, coords, vec4<f32>(rgb, alpha));
+}
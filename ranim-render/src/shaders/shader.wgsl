@@ -7,7 +7,7 @@ var<uniform> camera: CameraUniform;
 
 struct VertexInput {
     [[location(0)]] position: vec3<f32>;
-    [[location(1)]] color: vec3<f32>;
+    [[location(1)]] color: vec4<f32>;
 };
 struct InstanceInput {
     [[location(5)]] model_matrix_0: vec4<f32>;
@@ -35,7 +35,7 @@ fn vertex(
     );
 
     var out: VertexOutput;
-    out.color = instance.color * vec4<f32>(model.color, 1.0);
+    out.color = instance.color * model.color;
     // the good ole MVP matrix.
     out.clip_position = camera.view_proj * model_matrix * vec4<f32>(model.position, 1.0);
     return out;
@@ -45,5 +45,6 @@ fn vertex(
 
 [[stage(fragment)]]
 fn fragment(in: VertexOutput) -> [[location(0)]] vec4<f32> {
-    return in.color;
+    // blending expects premultiplied alpha
+    return vec4<f32>(in.color.rgb * in.color.a, in.color.a);
 }
\ No newline at end of file
@@ -0,0 +1,45 @@
+[[group(0), binding(0)]] var input_texture : texture_2d<f32>;
+[[group(0), binding(1)]] var output_texture : texture_storage_2d<rgba8unorm, write>;
+
+fn store_yuv(coords: vec2<i32>, rgb: vec3<f32>, alpha: f32) {
+    let y = dot(rgb, vec3<f32>(0.2578125, 0.50390625, 0.09765625)) + (16.0 / 255.0);
+    let u = dot(rgb, vec3<f32>(-0.1484375, -0.2890625, 0.4375)) + 0.5;
+    let v = dot(rgb, vec3<f32>(0.4375, -0.3671875, -0.0703125)) + 0.5;
+
+    textureStore(output_texture, coords, vec4<f32>(y, u, v, alpha));
+}
+
+// chroma is kept for every pixel; the encoder subsamples it to its pixel format
+[[stage(compute), workgroup_size(16, 16)]]
+fn yuv_main(
+  [[builtin(global_invocation_id)]] global_id: vec3<u32>,
+) {
+    let dimensions = textureDimensions(input_texture);
+    let coords = vec2<i32>(global_id.xy);
+    if(coords.x >= dimensions.x || coords.y >= dimensions.y) {
+        return;
+    }
+
+    // premultiplied, so anything transparent ends up over black
+    let pix = textureLoad(input_texture, coords.xy, 0);
+    store_yuv(coords, pix.rgb, 1.0);
+}
+
+// keeps alpha, with straight colors as video formats with alpha expect them
+[[stage(compute), workgroup_size(16, 16)]]
+fn yuva_main(
+  [[builtin(global_invocation_id)]] global_id: vec3<u32>,
+) {
+    let dimensions = textureDimensions(input_texture);
+    let coords = vec2<i32>(global_id.xy);
+    if(coords.x >= dimensions.x || coords.y >= dimensions.y) {
+        return;
+    }
+
+    let pix = textureLoad(input_texture, coords.xy, 0);
+    var rgb = vec3<f32>(0.0, 0.0, 0.0);
+    if (pix.a > 0.0) {
+        rgb = pix.rgb / pix.a;
+    }
+    store_yuv(coords, rgb, pix.a);
+}
@@ -40,3 +40,14 @@ pub fn pad_to_bytes_per_row_alignment(a: usize) -> usize {
     (a * PIXEL_STRIDE / COPY_BYTES_PER_ROW_ALIGNMENT as usize + 1)
         * COPY_BYTES_PER_ROW_ALIGNMENT as usize
 }
+
+/// Work groups needed to cover a texture of `size` with one invocation per pixel.
+pub fn compute_work_group_count(
+    size: Size,
+    (workgroup_width, workgroup_height): (u32, u32),
+) -> (u32, u32) {
+    let x = size.width.div_ceil(workgroup_width);
+    let y = size.height.div_ceil(workgroup_height);
+
+    (x, y)
+}
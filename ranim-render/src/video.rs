@@ -1,62 +1,119 @@
 use std::{
-    ffi::CString,
+    ffi::{CStr, CString},
+    fs,
     num::NonZeroU32,
     ops::{Index, IndexMut},
+    path::PathBuf,
+    ptr,
 };
 
-use color_eyre::Result;
+use color_eyre::{eyre::eyre, Result};
 use cstr::cstr;
+use ranim::{color::Color, scene::Scene};
 use rsmpeg::{
-    avcodec::{AVCodec, AVCodecContext},
+    avcodec::AVCodecContext,
     avformat::AVFormatContextOutput,
     avutil::{ra, AVDictionary, AVFrame},
     error::RsmpegError,
+    ffi,
 };
 
 use crate::{
     args::Args,
+    codec::{find_encoder, Codec, Encoder, EncoderConfig, TwoPass, YuvFormat},
     data::RenderData,
-    util::{Size, PIXEL_STRIDE},
-    RenderPass, Renderer, RgbTexture, TextureAndView,
+    output::{FrameWriter, ImageWriter},
+    util::{compute_work_group_count, Size},
+    RenderPass, Renderer, RgbBuffer, RgbTexture, TextureAndView,
 };
 
+/// Where [`VideoRenderer`] sends rendered frames.
+enum Output {
+    /// A video whose encoder is opened on the first frame, once its
+    /// background tells whether to keep alpha.
+    PendingVideo(Box<Args>),
+    /// Converted to YUV on the GPU and encoded by FFmpeg.
+    Video {
+        enc: VideoEncoder,
+        yuv_texture: YuvTexture,
+        yuv_buffer: YuvBuffer,
+        yuv_pass: YuvPass,
+    },
+    /// Read back from the RGB texture as they are, and written as images.
+    Images {
+        writer: ImageWriter,
+        rgb_buffer: RgbBuffer,
+    },
+    /// Rendered and dropped.
+    Discard,
+}
+
+/// Renders scenes to the output chosen by [`Args`]: a video, images or an animation, or nothing.
 pub struct VideoRenderer {
     renderer: Renderer,
     pub data: RenderData,
-    enc: VideoEncoder,
     rgb_texture: RgbTexture,
-    yuv_texture: YuvTexture,
-    yuv_buffer: YuvBuffer,
     render_pass: RenderPass,
-    yuv_pass: YuvPass,
+    output: Output,
+    single_frame: bool,
 }
 impl VideoRenderer {
     pub async fn new(args: Args) -> Result<Self> {
         let renderer = Renderer::new(&args).await?;
         let data = RenderData::new(&renderer);
-        let enc = VideoEncoder::new(&args)?;
         let rgb_texture = RgbTexture::new(&renderer);
-        let yuv_texture = YuvTexture::new(&renderer);
-        let yuv_buffer = YuvBuffer::new(&renderer);
-        let render_pass = RenderPass::new(&renderer, &data);
-        let yuv_pass = YuvPass::new(&renderer, &rgb_texture, &yuv_texture);
+        let render_pass = RenderPass::new(&renderer, &data, &rgb_texture);
+
+        let single_frame = args.single_frame;
+        let output = if args.no_output {
+            Output::Discard
+        } else if let Some(writer) = ImageWriter::new(&args)? {
+            Output::Images {
+                writer,
+                rgb_buffer: RgbBuffer::new(&renderer),
+            }
+        } else {
+            Output::PendingVideo(Box::new(args))
+        };
 
         Ok(Self {
             renderer,
             data,
-            enc,
             rgb_texture,
-            yuv_texture,
-            yuv_buffer,
             render_pass,
-            yuv_pass,
+            output,
+            single_frame,
         })
     }
+    /// Opens the video encoder if it is still pending, keeping alpha if the
+    /// video starts on a see-through `background` and the codec can encode it.
+    fn open_video(&mut self, background: Color) -> Result<()> {
+        let args = match &self.output {
+            Output::PendingVideo(args) => args,
+            _ => return Ok(()),
+        };
+        let enc = VideoEncoder::new(args, background.a < 1.0)?;
+        let yuv_texture = YuvTexture::new(&self.renderer);
+        self.output = Output::Video {
+            yuv_pass: YuvPass::new(
+                &self.renderer,
+                &self.rgb_texture,
+                &yuv_texture,
+                enc.has_alpha(),
+            ),
+            enc,
+            yuv_texture,
+            yuv_buffer: YuvBuffer::new(&self.renderer),
+        };
+        Ok(())
+    }
     pub fn update(&mut self) {
         self.data.update(&self.renderer);
     }
 
     pub async fn render(&mut self) -> Result<()> {
+        // the background the frame is cleared with
+        self.open_video(self.data.background)?;
         let mut encoder =
             self.renderer
                 .device
@@ -66,19 +123,85 @@ impl VideoRenderer {
         self.render_pass
             .execute(&mut encoder, &self.rgb_texture, &self.data);
 
-        self.yuv_pass
-            .execute(&mut encoder, &self.yuv_texture, &self.yuv_buffer);
+        match &mut self.output {
+            Output::Video {
+                yuv_texture,
+                yuv_buffer,
+                yuv_pass,
+                ..
+            } => yuv_pass.execute(&mut encoder, yuv_texture, yuv_buffer),
+            Output::Images { rgb_buffer, .. } => {
+                rgb_buffer.copy_from(&mut encoder, &self.rgb_texture)
+            }
+            Output::PendingVideo(_) => unreachable!("video is opened above"),
+            Output::Discard => {}
+        }
         self.renderer.queue.submit([encoder.finish()]);
 
-        let view = self.yuv_buffer.view(&self.renderer.device).await;
-        self.enc.encode(&view)?;
-        self.yuv_buffer.unmap(view);
+        match &mut self.output {
+            Output::Video {
+                enc, yuv_buffer, ..
+            } => {
+                let view = yuv_buffer.view(&self.renderer.device).await;
+                enc.encode(&view)?;
+                yuv_buffer.unmap(view);
+            }
+            Output::Images { writer, rgb_buffer } => {
+                let image = rgb_buffer.read(&self.renderer.device).await;
+                writer.write(&image)?;
+            }
+            Output::PendingVideo(_) => unreachable!("video is opened above"),
+            Output::Discard => self.renderer.device.poll(wgpu::Maintain::Wait),
+        }
 
         Ok(())
     }
 
+    /// Renders every frame recorded by `scene`, in order, or only the last
+    /// one for a single frame output. With two-pass encoding, the frames are
+    /// rendered twice, so only one scene can be rendered.
+    pub async fn render_scene(&mut self, scene: &Scene) -> Result<()> {
+        let mut frames = scene.frames();
+        if self.single_frame {
+            frames = &frames[frames.len().saturating_sub(1)..];
+        }
+        if let Some(frame) = frames.first() {
+            self.open_video(frame.background)?;
+        }
+        if matches!(&self.output, Output::Video { enc, .. } if !enc.has_alpha())
+            && frames.iter().any(|frame| frame.background.a < 1.0)
+        {
+            log::warn!(
+                "video output has no alpha channel, so the background is drawn opaque; \
+                 ProRes (.mov) and VP9 (.webm) keep it when rendering with a transparent background"
+            );
+        }
+        loop {
+            for frame in frames {
+                self.data.load_frame(frame);
+                self.update();
+                self.render().await?;
+            }
+            match &mut self.output {
+                Output::Video { enc, .. } if enc.is_first_pass() => enc.start_second_pass()?,
+                _ => break,
+            }
+        }
+        Ok(())
+    }
+
     pub fn conclude(&mut self) -> Result<()> {
-        self.enc.conclude()
+        if let Output::PendingVideo(args) = &self.output {
+            // nothing was rendered, so an empty video on the requested background
+            let background = args.background;
+            self.open_video(background)?;
+        }
+        match &mut self.output {
+            Output::Video { enc, .. } => enc.conclude(),
+            Output::Images { writer, .. } => writer.conclude(),
+            Output::PendingVideo(_) => unreachable!("video is opened above"),
+            Output::Discard => Ok(()),
+        }
     }
 }
 
@@ -152,6 +275,15 @@ impl<'a> FrameData<'a> {
         let buf = unsafe { std::slice::from_raw_parts_mut(data, linesize * height) };
         Self { buf, linesize }
     }
+    /// Writes the sample at `(x, y)`, as 16 bits little-endian if `wide`.
+    fn put(&mut self, x: usize, y: usize, sample: u32, wide: bool) {
+        if wide {
+            self[(x * 2, y)] = sample as u8;
+            self[(x * 2 + 1, y)] = (sample >> 8) as u8;
+        } else {
+            self[(x, y)] = sample as u8;
+        }
+    }
 }
 impl<'a> Index<(usize, usize)> for FrameData<'a> {
     type Output = u8;
@@ -166,46 +298,201 @@ impl<'a> IndexMut<(usize, usize)> for FrameData<'a> {
     }
 }
 
+/// Which pass an encoder is opened for.
+#[derive(Clone, Copy)]
+enum Pass<'a> {
+    Only,
+    First,
+    /// With the statistics of the first pass, for encoders that take them from FFmpeg.
+    Second(Option<&'a CStr>),
+}
+
+/// What [`VideoEncoder`] opens its encoder and output with.
+struct EncoderSetup {
+    codec: Codec,
+    config: EncoderConfig,
+    output_file: PathBuf,
+    size: Size,
+    frame_rate: u32,
+    yuv_format: YuvFormat,
+}
+impl EncoderSetup {
+    /// Where encoders with [`TwoPass::StatsFile`] keep statistics between passes.
+    fn stats_file(&self) -> PathBuf {
+        self.output_file.with_extension("2pass.log")
+    }
+
+    /// Opens the first of `encoders` that FFmpeg has and that opens, so that for example
+    /// NVENC falls back to a software encoder without an NVIDIA GPU. Encoders that can't do
+    /// two passes are skipped for those.
+    fn open_encoder<'e>(
+        &self,
+        encoders: impl IntoIterator<Item = &'e Encoder>,
+        pass: Pass<'_>,
+    ) -> Result<(AVCodecContext, &'e Encoder)> {
+        let two_pass = !matches!(pass, Pass::Only);
+        let mut error = None;
+        let mut skipped = false;
+        for encoder in encoders {
+            let av_encoder = match find_encoder(encoder.name) {
+                Some(av_encoder) => av_encoder,
+                None => continue,
+            };
+            if two_pass && encoder.two_pass == TwoPass::Unsupported {
+                skipped = true;
+                continue;
+            }
+            let mut ctx = AVCodecContext::new(&av_encoder);
+            ctx.set_width(self.size.width as i32);
+            ctx.set_height(self.size.height as i32);
+            ctx.set_time_base(ra(1, self.frame_rate as i32));
+            ctx.set_framerate(ra(self.frame_rate as i32, 1));
+            ctx.set_gop_size(self.config.gop as i32);
+            ctx.set_max_b_frames(self.config.b_frames as i32);
+            ctx.set_pix_fmt(self.yuv_format.pix_fmt());
+            match pass {
+                Pass::Only => {}
+                Pass::First => ctx.set_flags(ctx.flags | ffi::AV_CODEC_FLAG_PASS1 as i32),
+                Pass::Second(stats) => {
+                    ctx.set_flags(ctx.flags | ffi::AV_CODEC_FLAG_PASS2 as i32);
+                    if let Some(stats) = stats {
+                        // only read, and it outlives the context; see `VideoEncoder::stats`
+                        unsafe { (*ctx.as_mut_ptr()).stats_in = stats.as_ptr() as *mut _ };
+                    }
+                }
+            }
+
+            let alpha = self.yuv_format.has_alpha();
+            let mut options = self.config.options(self.codec, encoder, alpha);
+            if two_pass && encoder.two_pass == TwoPass::StatsFile {
+                let stats_file = self.stats_file().to_string_lossy().into_owned();
+                options.push(("stats".to_string(), stats_file));
+            }
+            let mut dict: Option<AVDictionary> = None;
+            for (key, value) in options {
+                let key = CString::new(key).unwrap();
+                let value = CString::new(value).unwrap();
+                dict = Some(match dict {
+                    Some(dict) => dict.set(&key, &value, 0),
+                    None => AVDictionary::new(&key, &value, 0),
+                });
+            }
+
+            match ctx.open(dict) {
+                Ok(ignored) => {
+                    if let Some(ignored) = ignored {
+                        log::warn!(
+                            "{} ignored options it doesn't have: {}",
+                            encoder.name,
+                            ignored.get_string(b'=', b',')?.to_string_lossy()
+                        );
+                    }
+                    return Ok((ctx, encoder));
+                }
+                Err(e) => {
+                    log::info!("could not open encoder {}: {e}", encoder.name);
+                    error = Some(e);
+                }
+            }
+        }
+        let codec = self.codec;
+        match error {
+            Some(e) => Err(e.into()),
+            None if skipped => Err(crate::Error::NoTwoPassEncoder { codec }.into()),
+            None => Err(crate::Error::NoEncoderFound {
+                codec,
+                available: Codec::available_encoders(),
+            }
+            .into()),
+        }
+    }
+
+    fn open_output(&self, encode_ctx: &AVCodecContext) -> Result<AVFormatContextOutput> {
+        let output_path = CString::new(self.output_file.to_string_lossy().as_ref()).unwrap();
+        let mut output_ctx = AVFormatContextOutput::create(&output_path, None)?;
+        {
+            let mut stream = output_ctx.new_stream();
+            // autodetect output format based on filename
+            stream.set_codecpar(encode_ctx.extract_codecpar());
+            stream.set_time_base(encode_ctx.time_base);
+        }
+        // put the index of MP4 and MOV files first, so they can play while they upload
+        if matches!(self.container().as_str(), "mp4" | "mov" | "m4v") {
+            unsafe {
+                ffi::av_opt_set(
+                    output_ctx.priv_data,
+                    cstr!("movflags").as_ptr(),
+                    cstr!("+faststart").as_ptr(),
+                    0,
+                );
+            }
+        }
+        output_ctx.dump(0, &output_path)?;
+        output_ctx.write_header()?;
+        Ok(output_ctx)
+    }
+    fn container(&self) -> String {
+        let extension = self.output_file.extension().unwrap_or_default();
+        extension.to_string_lossy().to_ascii_lowercase()
+    }
+}
+
 pub struct VideoEncoder {
     encode_ctx: AVCodecContext,
+    /// Statistics of the first pass for `encode_ctx` to read in the second, declared after it
+    /// so that they are dropped after it.
+    stats: Option<CString>,
     frame: AVFrame,
-    output_ctx: AVFormatContextOutput,
+    /// `None` during the first of two passes, whose packets are only needed for statistics.
+    output_ctx: Option<AVFormatContextOutput>,
 
-    size: Size,
+    setup: EncoderSetup,
+    encoder: &'static Encoder,
     frame_cnt: i64,
 }
 impl VideoEncoder {
-    pub fn new(args: &Args) -> Result<Self> {
-        let size = args.quality.size();
-        let frame_rate = args.quality.frame_rate();
+    /// Opens the encoder chosen by `args`, with an alpha channel if `alpha`
+    /// and the codec can encode one.
+    pub fn new(args: &Args, alpha: bool) -> Result<Self> {
         let mut output_file = args.output_file.clone();
 
+        let codec = match (args.codec, output_file.extension()) {
+            (Some(codec), _) => codec,
+            (None, Some(ext)) => Codec::for_container(&ext.to_string_lossy()),
+            (None, None) => Codec::H264,
+        };
         if output_file.extension().is_none() {
-            output_file.set_extension("mp4");
+            output_file.set_extension(codec.default_container());
+        }
+
+        let setup = EncoderSetup {
+            codec,
+            config: args.encoder_config(),
+            output_file,
+            size: args.quality.size(),
+            frame_rate: args.quality.frame_rate(),
+            yuv_format: codec.yuv_format(alpha),
+        };
+
+        // check before a first pass that would be wasted
+        let output_path = CString::new(setup.output_file.to_string_lossy().as_ref()).unwrap();
+        let supported = unsafe {
+            let format = ffi::av_guess_format(ptr::null(), output_path.as_ptr(), ptr::null());
+            // unknown formats are left for FFmpeg to report when the output is created
+            format.is_null()
+                || ffi::avformat_query_codec(format, codec.id(), ffi::FF_COMPLIANCE_NORMAL as i32)
+                    != 0
+        };
+        if !supported {
+            let container = setup.container();
+            return Err(crate::Error::UnsupportedContainer { codec, container }.into());
         }
 
-        let encode_ctx = {
-            let encoder = AVCodec::find_encoder_by_name(cstr!("h264_nvenc"))
-                .or_else(|| AVCodec::find_encoder_by_name(cstr!("libx264")))
-                .expect("Failed to find encoder codec");
-            let mut ctx = AVCodecContext::new(&encoder);
-            ctx.set_width(size.width as i32);
-            ctx.set_height(size.height as i32);
-            ctx.set_time_base(ra(1, frame_rate as i32));
-            ctx.set_framerate(ra(frame_rate as i32, 1));
-            ctx.set_gop_size(10);
-            ctx.set_max_b_frames(1);
-            ctx.set_pix_fmt(rsmpeg::ffi::AVPixelFormat_AV_PIX_FMT_YUV420P);
-            let dict = AVDictionary::from_string(
-                cstr!("crf=28,profile=high,preset=fast"),
-                cstr!("="),
-                cstr!(","),
-                0,
-            )
-            .expect("Failed to parse dictionary string");
-            ctx.open(Some(dict))?;
-            ctx
+        let pass = match setup.config.two_pass {
+            true => Pass::First,
+            false => Pass::Only,
         };
+        let (encode_ctx, encoder) = setup.open_encoder(codec.encoders(), pass)?;
 
         let mut frame = AVFrame::new();
         frame.set_format(encode_ctx.pix_fmt);
@@ -213,52 +500,99 @@ impl VideoEncoder {
         frame.set_height(encode_ctx.height);
         frame.alloc_buffer()?;
 
-        let output_ctx = {
-            let output_path = CString::new(output_file.to_string_lossy().as_ref()).unwrap();
-            let mut output_ctx = AVFormatContextOutput::create(&output_path, None)?;
-            {
-                let mut stream = output_ctx.new_stream();
-                // autodetect output format based on filename
-                stream.set_codecpar(encode_ctx.extract_codecpar());
-                stream.set_time_base(encode_ctx.time_base);
-            }
-            output_ctx.dump(0, &output_path)?;
-            output_ctx.write_header()?;
-            output_ctx
+        let output_ctx = match pass {
+            Pass::First => None,
+            _ => Some(setup.open_output(&encode_ctx)?),
         };
 
         Ok(Self {
             encode_ctx,
+            stats: None,
             frame,
             output_ctx,
-            size,
+            setup,
+            encoder,
             frame_cnt: 0,
         })
     }
 
+    /// Whether frames keep their alpha, so [`YuvPass`] has to write it.
+    pub fn has_alpha(&self) -> bool {
+        self.setup.yuv_format.has_alpha()
+    }
+
+    /// Whether this is the first of two passes, after which the same frames have to be encoded
+    /// again following [`start_second_pass`](Self::start_second_pass).
+    pub fn is_first_pass(&self) -> bool {
+        self.output_ctx.is_none()
+    }
+    pub fn start_second_pass(&mut self) -> Result<()> {
+        self.encode_ctx.send_frame(None)?;
+        self.write()?;
+
+        let stats_out = self.encode_ctx.stats_out;
+        if !stats_out.is_null() {
+            self.stats = Some(unsafe { CStr::from_ptr(stats_out) }.to_owned());
+        }
+        // close the first pass before opening the second, so x264 has finished its stats file;
+        // the context is freed when it's replaced
+        unsafe { ffi::avcodec_close(self.encode_ctx.as_mut_ptr()) };
+        let (encode_ctx, _) = self
+            .setup
+            .open_encoder([self.encoder], Pass::Second(self.stats.as_deref()))?;
+        self.encode_ctx = encode_ctx;
+        self.output_ctx = Some(self.setup.open_output(&self.encode_ctx)?);
+        self.frame_cnt = 0;
+        Ok(())
+    }
+
     pub fn encode(&mut self, buf: &YuvBufferView<'_>) -> Result<()> {
-        let width = self.size.width as usize;
-        let height = self.size.height as usize;
-        let bytes_per_row = self.size.bytes_per_row as usize;
+        let size = self.setup.size;
+        let width = size.width as usize;
+        let height = size.height as usize;
+        let bytes_per_row = size.bytes_per_row as usize;
+        let yuv_format = self.setup.yuv_format;
+        let (block_width, block_height) = yuv_format.chroma_block();
+        let wide = yuv_format.is_wide();
+
+        // the encoder may still hold on to the last frame
+        self.frame.make_writable()?;
         let mut dst_y = FrameData::new(&self.frame, 0, height);
-        let mut dst_u = FrameData::new(&self.frame, 1, height / 2);
-        let mut dst_v = FrameData::new(&self.frame, 2, height / 2);
+        let mut dst_u = FrameData::new(&self.frame, 1, height / block_height);
+        let mut dst_v = FrameData::new(&self.frame, 2, height / block_height);
+        let mut dst_a = yuv_format
+            .has_alpha()
+            .then(|| FrameData::new(&self.frame, 3, height));
+        let src = |x: usize, y: usize, channel: usize| {
+            buf.view[y * bytes_per_row + x * 4 + channel] as u32
+        };
+        // the mean of `count` 8-bit values, at the bit depth of the frame
+        let sample = |sum: u32, count: u32| match wide {
+            true => (sum * 4 + count / 2) / count,
+            false => (sum + count / 2) / count,
+        };
 
         // TODO: optimize this
-        for sy in 0..height / 2 {
-            for sx in 0..width / 2 {
-                let y = sy * 2;
-                let x = sx * 2;
-
-                dst_y[(x, y)] = buf.view[y * bytes_per_row + x * 4];
-                dst_y[(x + 1, y)] = buf.view[y * bytes_per_row + (x + 1) * 4];
-                dst_y[(x, y + 1)] = buf.view[(y + 1) * bytes_per_row + x * 4];
-                dst_y[(x + 1, y + 1)] = buf.view[(y + 1) * bytes_per_row + (x + 1) * 4];
-
-                let u = buf.view[y * bytes_per_row + x * 4 + 1];
-                let v = buf.view[y * bytes_per_row + x * 4 + 2];
-                dst_u[(sx, sy)] = u;
-                dst_v[(sx, sy)] = v;
+        for y in 0..height {
+            for x in 0..width {
+                dst_y.put(x, y, sample(src(x, y, 0), 1), wide);
+                if let Some(dst_a) = &mut dst_a {
+                    dst_a.put(x, y, sample(src(x, y, 3), 1), wide);
+                }
+            }
+        }
+        for sy in 0..height / block_height {
+            for sx in 0..width / block_width {
+                let (mut u, mut v) = (0, 0);
+                for y in sy * block_height..(sy + 1) * block_height {
+                    for x in sx * block_width..(sx + 1) * block_width {
+                        u += src(x, y, 1);
+                        v += src(x, y, 2);
+                    }
+                }
+                let count = (block_width * block_height) as u32;
+                dst_u.put(sx, sy, sample(u, count), wide);
+                dst_v.put(sx, sy, sample(v, count), wide);
             }
         }
 
@@ -270,9 +604,21 @@ impl VideoEncoder {
     }
 
     fn conclude(&mut self) -> Result<()> {
+        if self.is_first_pass() {
+            return Err(eyre!(
+                "two-pass encoding needs every frame twice, as VideoRenderer::render_scene gives them"
+            ));
+        }
         self.encode_ctx.send_frame(None)?;
         self.write()?;
-        self.output_ctx.write_trailer()?;
+        self.output_ctx.as_mut().unwrap().write_trailer()?;
+
+        if self.encoder.two_pass == TwoPass::StatsFile {
+            // x264 also writes macroblock statistics next to its own
+            let stats_file = self.setup.stats_file();
+            let _ = fs::remove_file(format!("{}.mbtree", stats_file.display()));
+            let _ = fs::remove_file(stats_file);
+        }
         Ok(())
     }
 
@@ -285,11 +631,14 @@ impl VideoEncoder {
                 }
                 Err(e) => return Err(e.into()),
             };
-            packet.rescale_ts(
-                self.encode_ctx.time_base,
-                self.output_ctx.streams().get(0).unwrap().time_base,
-            );
-            self.output_ctx.write_frame(&mut packet)?;
+            // packets of a first pass are dropped
+            if let Some(output_ctx) = &mut self.output_ctx {
+                packet.rescale_ts(
+                    self.encode_ctx.time_base,
+                    output_ctx.streams().get(0).unwrap().time_base,
+                );
+                output_ctx.write_frame(&mut packet)?;
+            }
         }
         Ok(())
     }
@@ -303,17 +652,19 @@ pub struct YuvPass {
     size: Size,
 }
 impl YuvPass {
-    pub fn new(renderer: &Renderer, rgb: &RgbTexture, yuv: &YuvTexture) -> Self {
+    /// Converts `rgb` into `yuv`, with straight alpha in its fourth channel if `alpha`, or
+    /// over black otherwise.
+    pub fn new(renderer: &Renderer, rgb: &RgbTexture, yuv: &YuvTexture, alpha: bool) -> Self {
         let shader = renderer
             .device
-            .create_shader_module(&wgpu::include_wgsl!("shaders/yuv420.wgsl"));
+            .create_shader_module(&wgpu::include_wgsl!("shaders/yuv.wgsl"));
         let pipeline = renderer
             .device
             .create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
                 label: Some("YUV pipeline"),
                 layout: None,
                 module: &shader,
-                entry_point: "yuv_main",
+                entry_point: if alpha { "yuva_main" } else { "yuv_main" },
             });
         let (dispatch_x, dispatch_y) = compute_work_group_count(renderer.size, (16, 16));
 
@@ -375,14 +726,4 @@ impl YuvPass {
             self.size.extent(),
         );
     }
-}
-
-fn compute_work_group_count(
-    size: Size,
-    (workgroup_width, workgroup_height): (u32, u32),
-) -> (u32, u32) {
-    let x = (size.bytes_per_row / PIXEL_STRIDE as u32 + workgroup_width - 1) / workgroup_width;
-    let y = (size.height + workgroup_height - 1) / workgroup_height;
-
-    (x, y)
-}
+}
\ No newline at end of file
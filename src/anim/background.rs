@@ -0,0 +1,41 @@
+use crate::{
+    color::{Color, ColorSpace},
+    scene::graph::SceneGraph,
+};
+
+use super::{Animation, AnimationSettings};
+
+/// Changes the background of the scene to `target`, which may be
+/// transparent.
+pub struct ChangeBackground {
+    target: Color,
+    start: Color,
+    /// Space the colors are mixed in on the way.
+    pub color_space: ColorSpace,
+    pub settings: AnimationSettings,
+}
+impl ChangeBackground {
+    pub fn new(target: Color) -> Self {
+        Self {
+            target,
+            start: target,
+            color_space: ColorSpace::default(),
+            settings: AnimationSettings::default(),
+        }
+    }
+}
+
+impl Animation for ChangeBackground {
+    fn settings(&self) -> &AnimationSettings {
+        &self.settings
+    }
+
+    fn begin(&mut self, graph: &mut SceneGraph) {
+        self.start = graph.background();
+    }
+
+    fn interpolate(&mut self, graph: &mut SceneGraph, alpha: f32) {
+        let alpha = self.settings.alpha(alpha);
+        graph.set_background(self.start.interpolate(self.target, alpha, self.color_space));
+    }
+}
@@ -0,0 +1,231 @@
+use crate::scene::graph::SceneGraph;
+
+use super::{rate, Animation, AnimationSettings};
+
+/// Plays several animations together as one.
+///
+/// Each animation starts once the previous one is `lag_ratio` of the way
+/// through, so a lag ratio of 0 plays them all in parallel and 1 plays them
+/// one after another. The run time defaults to the time it takes for the
+/// last one to finish; changing it stretches the whole group.
+///
+/// Each animation is only begun once the group reaches its start time, and
+/// finished as soon as the group reaches its end time, so animations that
+/// play one after another on the same mobject each pick up where the
+/// previous one left off.
+pub struct AnimationGroup {
+    animations: Vec<Box<dyn Animation>>,
+    /// Start and end time of each animation, in seconds.
+    timings: Vec<(f32, f32)>,
+    /// Whether each animation has been begun or finished yet.
+    states: Vec<ChildState>,
+    /// Time at which the last animation ends.
+    total_time: f32,
+    pub settings: AnimationSettings,
+}
+impl AnimationGroup {
+    pub fn new(animations: Vec<Box<dyn Animation>>) -> Self {
+        Self::with_lag_ratio(animations, 0.0)
+    }
+    pub fn with_lag_ratio(animations: Vec<Box<dyn Animation>>, lag_ratio: f32) -> Self {
+        let mut timings = Vec::with_capacity(animations.len());
+        let mut start = 0.0;
+        for animation in &animations {
+            let end = start + animation.run_time();
+            timings.push((start, end));
+            start += (end - start) * lag_ratio;
+        }
+        let total_time = timings.iter().map(|&(_, end)| end).fold(0.0, f32::max);
+
+        Self {
+            states: vec![ChildState::Pending; animations.len()],
+            animations,
+            timings,
+            total_time,
+            settings: AnimationSettings {
+                run_time: total_time,
+                rate_func: rate::linear.into(),
+            },
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum ChildState {
+    Pending,
+    Running,
+    Finished,
+}
+
+impl Animation for AnimationGroup {
+    fn settings(&self) -> &AnimationSettings {
+        &self.settings
+    }
+
+    fn begin(&mut self, graph: &mut SceneGraph) {
+        self.states.fill(ChildState::Pending);
+        self.interpolate(graph, 0.0);
+    }
+
+    fn interpolate(&mut self, graph: &mut SceneGraph, alpha: f32) {
+        let time = self.settings.alpha(alpha) * self.total_time;
+        let children = self.animations.iter_mut().zip(&mut self.states);
+        for ((animation, state), &(start, end)) in children.zip(&self.timings) {
+            if *state == ChildState::Pending && time >= start {
+                animation.begin(graph);
+                *state = ChildState::Running;
+            }
+            if *state != ChildState::Running {
+                continue;
+            }
+            if time >= end {
+                animation.interpolate(graph, 1.0);
+                animation.finish(graph);
+                *state = ChildState::Finished;
+            } else {
+                animation.interpolate(graph, (time - start) / (end - start));
+            }
+        }
+    }
+
+    fn finish(&mut self, graph: &mut SceneGraph) {
+        for (animation, state) in self.animations.iter_mut().zip(&mut self.states) {
+            if *state == ChildState::Pending {
+                animation.begin(graph);
+            }
+            if *state != ChildState::Finished {
+                animation.interpolate(graph, 1.0);
+                animation.finish(graph);
+                *state = ChildState::Finished;
+            }
+        }
+    }
+}
+
+macro_rules! delegate_animation {
+    ($ty:ident) => {
+        impl Animation for $ty {
+            fn settings(&self) -> &AnimationSettings {
+                self.0.settings()
+            }
+            fn begin(&mut self, graph: &mut SceneGraph) {
+                self.0.begin(graph)
+            }
+            fn interpolate(&mut self, graph: &mut SceneGraph, alpha: f32) {
+                self.0.interpolate(graph, alpha)
+            }
+            fn finish(&mut self, graph: &mut SceneGraph) {
+                self.0.finish(graph)
+            }
+        }
+    };
+}
+
+/// Plays animations one after another.
+pub struct Succession(pub AnimationGroup);
+impl Succession {
+    pub fn new(animations: Vec<Box<dyn Animation>>) -> Self {
+        Self(AnimationGroup::with_lag_ratio(animations, 1.0))
+    }
+}
+delegate_animation!(Succession);
+
+/// Starts animations one shortly after another, so they overlap.
+pub struct LaggedStart(pub AnimationGroup);
+impl LaggedStart {
+    pub const DEFAULT_LAG_RATIO: f32 = 0.05;
+
+    pub fn new(animations: Vec<Box<dyn Animation>>) -> Self {
+        Self::with_lag_ratio(animations, Self::DEFAULT_LAG_RATIO)
+    }
+    pub fn with_lag_ratio(animations: Vec<Box<dyn Animation>>, lag_ratio: f32) -> Self {
+        Self(AnimationGroup::with_lag_ratio(animations, lag_ratio))
+    }
+}
+delegate_animation!(LaggedStart);
+
+#[cfg(test)]
+mod tests {
+    use std::{cell::RefCell, rc::Rc};
+
+    use super::*;
+
+    type Log = Rc<RefCell<Vec<(char, &'static str, f32)>>>;
+
+    struct Record {
+        name: char,
+        log: Log,
+        settings: AnimationSettings,
+    }
+    impl Animation for Record {
+        fn settings(&self) -> &AnimationSettings {
+            &self.settings
+        }
+        fn begin(&mut self, _graph: &mut SceneGraph) {
+            self.log.borrow_mut().push((self.name, "begin", 0.0));
+        }
+        fn interpolate(&mut self, _graph: &mut SceneGraph, alpha: f32) {
+            self.log
+                .borrow_mut()
+                .push((self.name, "interpolate", alpha));
+        }
+        fn finish(&mut self, _graph: &mut SceneGraph) {
+            self.log.borrow_mut().push((self.name, "finish", 1.0));
+        }
+    }
+
+    fn play(group: &mut dyn Animation, frames: usize) {
+        let mut graph = SceneGraph::new();
+        group.begin(&mut graph);
+        for i in 0..=frames {
+            group.interpolate(&mut graph, i as f32 / frames as f32);
+        }
+        group.finish(&mut graph);
+    }
+
+    fn children(log: &Log) -> Vec<Box<dyn Animation>> {
+        ['a', 'b']
+            .map(|name| {
+                Box::new(Record {
+                    name,
+                    log: log.clone(),
+                    settings: AnimationSettings::default(),
+                }) as Box<dyn Animation>
+            })
+            .into()
+    }
+
+    #[test]
+    fn succession_runs_children_in_turn() {
+        let log = Log::default();
+        play(&mut Succession::new(children(&log)), 4);
+        let log = log.borrow();
+
+        let a_finish = log.iter().position(|e| *e == ('a', "finish", 1.0));
+        let b_begin = log.iter().position(|e| e.0 == 'b' && e.1 == "begin");
+        assert!(a_finish.unwrap() < b_begin.unwrap());
+        assert!(log[..b_begin.unwrap()].iter().all(|e| e.0 == 'a'));
+        assert!(log[b_begin.unwrap()..].iter().all(|e| e.0 == 'b'));
+
+        for name in ['a', 'b'] {
+            let count = |kind| log.iter().filter(|e| e.0 == name && e.1 == kind).count();
+            assert_eq!(count("begin"), 1);
+            assert_eq!(count("finish"), 1);
+        }
+        let last_b = log.iter().rev().find(|e| e.1 == "interpolate").unwrap();
+        assert_eq!(*last_b, ('b', "interpolate", 1.0));
+    }
+
+    #[test]
+    fn parallel_children_run_together() {
+        let log = Log::default();
+        play(&mut AnimationGroup::new(children(&log)), 2);
+        let log = log.borrow();
+
+        assert_eq!(log[0], ('a', "begin", 0.0));
+        assert_eq!(log[2], ('b', "begin", 0.0));
+        let b_halfway = log.iter().position(|e| *e == ('b', "interpolate", 0.5));
+        let a_finish = log.iter().position(|e| e.0 == 'a' && e.1 == "finish");
+        assert!(b_halfway.unwrap() < a_finish.unwrap());
+    }
+}
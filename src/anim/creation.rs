@@ -1,7 +1,95 @@
-use super::Animation;
+use crate::{
+    mobj::vectorized::{VMobject, Vectorized},
+    scene::graph::{Handle, SceneGraph},
+};
 
-pub struct Create {}
+use super::{Animation, AnimationSettings};
 
-impl Animation for Create {
+/// Draws the outline of a [`Vectorized`] mobject from its start to its end, showing it
+/// first if it isn't already.
+pub struct Create<M = VMobject> {
+    mobject: Handle<M>,
+    original: VMobject,
+    pub settings: AnimationSettings,
+}
+impl<M: Vectorized> Create<M> {
+    pub fn new(mobject: Handle<M>) -> Self {
+        Self {
+            mobject,
+            original: VMobject::new(),
+            settings: AnimationSettings::default(),
+        }
+    }
+}
 
-}
\ No newline at end of file
+impl<M: Vectorized> Animation for Create<M> {
+    fn settings(&self) -> &AnimationSettings {
+        &self.settings
+    }
+
+    fn begin(&mut self, graph: &mut SceneGraph) {
+        self.original.clone_from(graph.get(self.mobject).as_ref());
+        if !graph.is_shown(self.mobject) {
+            graph.show(self.mobject);
+        }
+    }
+
+    fn interpolate(&mut self, graph: &mut SceneGraph, alpha: f32) {
+        let alpha = self.settings.alpha(alpha);
+        graph
+            .get_mut(self.mobject)
+            .as_mut()
+            .pointwise_become_partial(&self.original, 0.0, alpha);
+    }
+
+    fn finish(&mut self, graph: &mut SceneGraph) {
+        graph
+            .get_mut(self.mobject)
+            .as_mut()
+            .clone_from(&self.original);
+    }
+}
+
+/// The reverse of [`Create`]: erases the outline of a [`Vectorized`] mobject from its
+/// end back to its start, then removes it. The mobject keeps its shape, so
+/// it can be shown again later.
+pub struct Uncreate<M = VMobject> {
+    mobject: Handle<M>,
+    original: VMobject,
+    pub settings: AnimationSettings,
+}
+impl<M: Vectorized> Uncreate<M> {
+    pub fn new(mobject: Handle<M>) -> Self {
+        Self {
+            mobject,
+            original: VMobject::new(),
+            settings: AnimationSettings::default(),
+        }
+    }
+}
+
+impl<M: Vectorized> Animation for Uncreate<M> {
+    fn settings(&self) -> &AnimationSettings {
+        &self.settings
+    }
+
+    fn begin(&mut self, graph: &mut SceneGraph) {
+        self.original.clone_from(graph.get(self.mobject).as_ref());
+    }
+
+    fn interpolate(&mut self, graph: &mut SceneGraph, alpha: f32) {
+        let alpha = self.settings.alpha(alpha);
+        graph
+            .get_mut(self.mobject)
+            .as_mut()
+            .pointwise_become_partial(&self.original, 0.0, 1.0 - alpha);
+    }
+
+    fn finish(&mut self, graph: &mut SceneGraph) {
+        graph
+            .get_mut(self.mobject)
+            .as_mut()
+            .clone_from(&self.original);
+        graph.remove(self.mobject);
+    }
+}
@@ -1,7 +1,53 @@
+use crate::scene::graph::SceneGraph;
+
+use self::rate::RateFunc;
+
+pub mod background;
+pub mod composition;
 pub mod creation;
+pub mod rate;
+pub mod transform;
 
-pub trait Animation {}
+/// Something that changes the mobjects of a scene over a fixed run time.
+///
+/// [`Scene::play`](crate::scene::Scene::play) calls [`begin`](Self::begin)
+/// once, then [`interpolate`](Self::interpolate) once per frame with the
+/// linear progress `alpha` going from 0 towards 1, and finally
+/// [`finish`](Self::finish). Animations refer to the mobjects they change by
+/// [`Handle`](crate::scene::graph::Handle).
+pub trait Animation {
+    fn settings(&self) -> &AnimationSettings;
 
-pub struct AnimationSettings {
+    fn begin(&mut self, _graph: &mut SceneGraph) {}
+    /// Moves the animation to the linear progress `alpha`. Implementations
+    /// are expected to pass it through [`AnimationSettings::alpha`] first.
+    fn interpolate(&mut self, graph: &mut SceneGraph, alpha: f32);
+    fn finish(&mut self, graph: &mut SceneGraph) {
+        self.interpolate(graph, 1.0);
+    }
 
-}
\ No newline at end of file
+    fn run_time(&self) -> f32 {
+        self.settings().run_time
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct AnimationSettings {
+    /// Duration in seconds.
+    pub run_time: f32,
+    pub rate_func: RateFunc,
+}
+impl AnimationSettings {
+    /// Applies the rate function to the linear progress `alpha`.
+    pub fn alpha(&self, alpha: f32) -> f32 {
+        self.rate_func.apply(alpha.clamp(0.0, 1.0))
+    }
+}
+impl Default for AnimationSettings {
+    fn default() -> Self {
+        Self {
+            run_time: 1.0,
+            rate_func: RateFunc::default(),
+        }
+    }
+}
@@ -0,0 +1,158 @@
+//! Easing functions from CSS: `cubic-bezier()`, `steps()` and the named
+//! keywords built on them.
+
+use super::RateFunc;
+
+/// Where the jumps of [`steps`] happen, as in CSS `steps(n, <jumpterm>)`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum StepPosition {
+    /// Jumps at the start of each interval, so the first value is above 0.
+    JumpStart,
+    /// Jumps at the end of each interval, so the last value is below 1.
+    #[default]
+    JumpEnd,
+    /// Holds at both 0 and 1, with `n - 1` jumps in between.
+    JumpNone,
+    /// Jumps at both ends, with `n + 1` jumps in total.
+    JumpBoth,
+}
+
+/// The CSS `cubic-bezier(x1, y1, x2, y2)` timing function: a cubic Bezier
+/// from `(0, 0)` to `(1, 1)` with the given control points, evaluated at
+/// `x = t`. `x1` and `x2` are clamped to `[0, 1]` so the curve stays a
+/// function.
+pub fn cubic_bezier(x1: f32, y1: f32, x2: f32, y2: f32) -> RateFunc {
+    let x1 = x1.clamp(0.0, 1.0);
+    let x2 = x2.clamp(0.0, 1.0);
+    let sample = move |a: f32, b: f32, s: f32| {
+        let r = 1.0 - s;
+        3.0 * r * r * s * a + 3.0 * r * s * s * b + s * s * s
+    };
+    let slope = move |a: f32, b: f32, s: f32| {
+        let r = 1.0 - s;
+        3.0 * r * r * a + 6.0 * r * s * (b - a) + 3.0 * s * s * (1.0 - b)
+    };
+
+    RateFunc::new(move |t| {
+        if t <= 0.0 || t >= 1.0 {
+            return t.clamp(0.0, 1.0);
+        }
+        // Newton's method first, which converges in a few steps for most
+        // curves, then bisection where the slope is too flat.
+        let mut s = t;
+        for _ in 0..8 {
+            let err = sample(x1, x2, s) - t;
+            if err.abs() < 1e-6 {
+                return sample(y1, y2, s);
+            }
+            let d = slope(x1, x2, s);
+            if d.abs() < 1e-6 {
+                break;
+            }
+            s = (s - err / d).clamp(0.0, 1.0);
+        }
+        let (mut lo, mut hi) = (0.0, 1.0);
+        s = t;
+        for _ in 0..32 {
+            let x = sample(x1, x2, s);
+            if (x - t).abs() < 1e-6 {
+                break;
+            }
+            if x < t {
+                lo = s;
+            } else {
+                hi = s;
+            }
+            s = (lo + hi) / 2.0;
+        }
+        sample(y1, y2, s)
+    })
+}
+
+/// The CSS `steps(n, position)` timing function, which jumps between `n`
+/// equally spaced levels instead of moving continuously.
+pub fn steps(n: u32, position: StepPosition) -> RateFunc {
+    let n = n.max(1) as f32;
+    RateFunc::new(move |t| {
+        let t = t.clamp(0.0, 1.0);
+        let (jumps, offset) = match position {
+            StepPosition::JumpStart => (n, 1.0),
+            StepPosition::JumpEnd => (n, 0.0),
+            StepPosition::JumpNone => ((n - 1.0).max(1.0), 0.0),
+            StepPosition::JumpBoth => (n + 1.0, 1.0),
+        };
+        let step = ((t * n).floor() + offset).min(n + offset);
+        (step / jumps).min(1.0)
+    })
+}
+
+/// CSS `ease`.
+pub fn ease() -> RateFunc {
+    cubic_bezier(0.25, 0.1, 0.25, 1.0)
+}
+/// CSS `ease-in`.
+pub fn ease_in() -> RateFunc {
+    cubic_bezier(0.42, 0.0, 1.0, 1.0)
+}
+/// CSS `ease-out`.
+pub fn ease_out() -> RateFunc {
+    cubic_bezier(0.0, 0.0, 0.58, 1.0)
+}
+/// CSS `ease-in-out`.
+pub fn ease_in_out() -> RateFunc {
+    cubic_bezier(0.42, 0.0, 0.58, 1.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(actual: f32, expected: f32) {
+        assert!((actual - expected).abs() < 1e-4, "{actual} != {expected}");
+    }
+
+    #[test]
+    fn named_ends() {
+        for f in [ease(), ease_in(), ease_out(), ease_in_out()] {
+            assert_eq!(f.apply(0.0), 0.0);
+            assert_eq!(f.apply(1.0), 1.0);
+        }
+    }
+
+    #[test]
+    fn ease_samples() {
+        let f = cubic_bezier(0.25, 0.1, 0.25, 1.0);
+        for (t, y) in [
+            (0.1, 0.094796),
+            (0.25, 0.408511),
+            (0.5, 0.802403),
+            (0.75, 0.960459),
+            (0.9, 0.994316),
+        ] {
+            assert_close(f.apply(t), y);
+        }
+        // with control points on the diagonal the curve is the identity
+        let linear = cubic_bezier(1.0 / 3.0, 1.0 / 3.0, 2.0 / 3.0, 2.0 / 3.0);
+        assert_close(linear.apply(0.3), 0.3);
+    }
+
+    #[test]
+    fn step_positions() {
+        let below = |t: f32| t - 1e-4;
+        // (position, f(0), f(just below 1/4), f(1/4), f(just below 1), f(1))
+        let cases = [
+            (StepPosition::JumpStart, 0.25, 0.25, 0.5, 1.0, 1.0),
+            (StepPosition::JumpEnd, 0.0, 0.0, 0.25, 0.75, 1.0),
+            (StepPosition::JumpNone, 0.0, 0.0, 1.0 / 3.0, 1.0, 1.0),
+            (StepPosition::JumpBoth, 0.2, 0.2, 0.4, 0.8, 1.0),
+        ];
+        for (position, start, before_edge, edge, before_end, end) in cases {
+            let f = steps(4, position);
+            assert_close(f.apply(0.0), start);
+            assert_close(f.apply(below(0.25)), before_edge);
+            assert_close(f.apply(0.25), edge);
+            assert_close(f.apply(below(1.0)), before_end);
+            assert_close(f.apply(1.0), end);
+        }
+    }
+}
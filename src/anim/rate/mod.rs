@@ -0,0 +1,261 @@
+//! Rate functions, which map the linear progress of an animation to the
+//! progress its mobjects are drawn at.
+//!
+//! Plain functions such as [`smooth`] convert into a [`RateFunc`] with
+//! `.into()`; parameterized ones such as [`cubic_bezier`] build one directly.
+
+use std::{f32::consts::PI, fmt, sync::Arc};
+
+mod css;
+mod penner;
+
+pub use self::{css::*, penner::*};
+
+/// Maps the linear progress of an animation, in `[0, 1]`, to the progress
+/// its mobjects are drawn at.
+#[derive(Clone)]
+pub struct RateFunc(Arc<dyn Fn(f32) -> f32 + Send + Sync>);
+impl RateFunc {
+    pub fn new(f: impl Fn(f32) -> f32 + Send + Sync + 'static) -> Self {
+        Self(Arc::new(f))
+    }
+    pub fn apply(&self, t: f32) -> f32 {
+        (self.0)(t)
+    }
+
+    /// Applies `self` to the output of `inner`.
+    pub fn compose(self, inner: impl Into<RateFunc>) -> Self {
+        let inner = inner.into();
+        Self::new(move |t| self.apply(inner.apply(t)))
+    }
+    /// Runs this rate function backwards in time.
+    pub fn reversed(self) -> Self {
+        Self::new(move |t| self.apply(1.0 - t))
+    }
+    /// Mirrors this rate function, turning an ease in into an ease out and
+    /// vice versa.
+    pub fn inverted(self) -> Self {
+        Self::new(move |t| 1.0 - self.apply(1.0 - t))
+    }
+    /// Runs this rate function in the first half and its reverse in the
+    /// second half.
+    pub fn there_and_back(self) -> Self {
+        Self::new(move |t| self.apply(if t < 0.5 { 2.0 * t } else { 2.0 * (1.0 - t) }))
+    }
+    /// Runs this rate function over the first half of the run time and
+    /// `other` over the second, each covering half of the progress.
+    pub fn chain(self, other: impl Into<RateFunc>) -> Self {
+        let other = other.into();
+        Self::new(move |t| {
+            if t < 0.5 {
+                0.5 * self.apply(2.0 * t)
+            } else {
+                0.5 + 0.5 * other.apply(2.0 * t - 1.0)
+            }
+        })
+    }
+}
+impl<F: Fn(f32) -> f32 + Send + Sync + 'static> From<F> for RateFunc {
+    fn from(f: F) -> Self {
+        Self::new(f)
+    }
+}
+impl Default for RateFunc {
+    fn default() -> Self {
+        Self::new(smooth)
+    }
+}
+impl fmt::Debug for RateFunc {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("RateFunc")
+    }
+}
+
+// manim's standard rate functions
+
+pub fn linear(t: f32) -> f32 {
+    t
+}
+
+/// A sigmoid-like ease in and out, with zero slope at both ends.
+pub fn smooth(t: f32) -> f32 {
+    let t = t.clamp(0.0, 1.0);
+    let s = 1.0 - t;
+    t * t * t * (10.0 * s * s + 5.0 * s * t + t * t)
+}
+
+/// The first half of [`smooth`], stretched over the whole run time.
+pub fn rush_into(t: f32) -> f32 {
+    2.0 * smooth(t / 2.0)
+}
+
+/// The second half of [`smooth`], stretched over the whole run time.
+pub fn rush_from(t: f32) -> f32 {
+    2.0 * smooth(t / 2.0 + 0.5) - 1.0
+}
+
+/// Starts fast and slows down along a quarter circle.
+pub fn slow_into(t: f32) -> f32 {
+    (1.0 - (1.0 - t) * (1.0 - t)).max(0.0).sqrt()
+}
+
+/// [`smooth`] twice in a row, pausing briefly at the midpoint.
+pub fn double_smooth(t: f32) -> f32 {
+    if t < 0.5 {
+        0.5 * smooth(2.0 * t)
+    } else {
+        0.5 * (1.0 + smooth(2.0 * t - 1.0))
+    }
+}
+
+/// Goes from 0 to 1 and back to 0, smoothly.
+pub fn there_and_back(t: f32) -> f32 {
+    smooth(if t < 0.5 { 2.0 * t } else { 2.0 * (1.0 - t) })
+}
+
+/// Like [`there_and_back`], but holding still at 1 for the middle third.
+pub fn there_and_back_with_pause(t: f32) -> f32 {
+    let a = 1.0 / 3.0;
+    if t < a {
+        smooth(t / a)
+    } else if t < 1.0 - a {
+        1.0
+    } else {
+        smooth((1.0 - t) / a)
+    }
+}
+
+/// Oscillates twice around 0 with a smooth envelope, ending where it started.
+pub fn wiggle(t: f32) -> f32 {
+    there_and_back(t) * (2.0 * PI * t).sin()
+}
+
+/// Pulls back a bit before heading to 1.
+pub fn running_start(t: f32) -> f32 {
+    // degree-6 Bezier with control values [0, 0, -0.5, -0.5, 1, 1, 1]
+    const CONTROL: [f32; 7] = [0.0, 0.0, -0.5, -0.5, 1.0, 1.0, 1.0];
+    const BINOMIAL: [f32; 7] = [1.0, 6.0, 15.0, 20.0, 15.0, 6.0, 1.0];
+    let s = 1.0 - t;
+    (0..7)
+        .map(|k| BINOMIAL[k] * s.powi(6 - k as i32) * t.powi(k as i32) * CONTROL[k])
+        .sum()
+}
+
+/// Reaches 1 at 80% of the run time, then holds.
+pub fn lingering(t: f32) -> f32 {
+    (t / 0.8).clamp(0.0, 1.0)
+}
+
+/// Approaches 1 exponentially, with a time constant of a tenth of the run
+/// time.
+pub fn exponential_decay(t: f32) -> f32 {
+    1.0 - (-t / 0.1).exp()
+}
+
+// Composition helpers
+
+/// Runs `f` within `[a, b]` of the run time, holding at `f(0)` before and
+/// `f(1)` after.
+pub fn squish(f: impl Into<RateFunc>, a: f32, b: f32) -> RateFunc {
+    let f = f.into();
+    RateFunc::new(move |t| {
+        if a == b {
+            return if t < a { f.apply(0.0) } else { f.apply(1.0) };
+        }
+        f.apply(((t - a) / (b - a)).clamp(0.0, 1.0))
+    })
+}
+
+/// Scales `f` so that it only reaches `proportion` of the way.
+pub fn not_quite_there(f: impl Into<RateFunc>, proportion: f32) -> RateFunc {
+    let f = f.into();
+    RateFunc::new(move |t| proportion * f.apply(t))
+}
+
+/// Like [`wiggle`], with a configurable number of oscillations.
+pub fn wiggle_n(wiggles: f32) -> RateFunc {
+    RateFunc::new(move |t| there_and_back(t) * (wiggles * PI * t).sin())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    type Named = (&'static str, fn(f32) -> f32);
+
+    fn assert_ends(name: &str, f: fn(f32) -> f32) {
+        assert!(f(0.0).abs() < 1e-5, "{name}(0) = {}", f(0.0));
+        assert!((f(1.0) - 1.0).abs() < 1e-5, "{name}(1) = {}", f(1.0));
+    }
+
+    #[test]
+    fn standard_ends() {
+        let funcs: [Named; 8] = [
+            ("linear", linear),
+            ("smooth", smooth),
+            ("rush_into", rush_into),
+            ("rush_from", rush_from),
+            ("slow_into", slow_into),
+            ("double_smooth", double_smooth),
+            ("running_start", running_start),
+            ("lingering", lingering),
+        ];
+        for (name, f) in funcs {
+            assert_ends(name, f);
+        }
+        for f in [there_and_back, there_and_back_with_pause, wiggle] {
+            assert_eq!(f(0.0), 0.0);
+            assert!(f(1.0).abs() < 1e-5);
+        }
+        assert_eq!(exponential_decay(0.0), 0.0);
+        assert!((exponential_decay(1.0) - 1.0).abs() < 1e-4);
+    }
+
+    #[test]
+    fn penner_ends() {
+        let funcs: [Named; 30] = [
+            ("ease_in_sine", ease_in_sine),
+            ("ease_out_sine", ease_out_sine),
+            ("ease_in_out_sine", ease_in_out_sine),
+            ("ease_in_quad", ease_in_quad),
+            ("ease_out_quad", ease_out_quad),
+            ("ease_in_out_quad", ease_in_out_quad),
+            ("ease_in_cubic", ease_in_cubic),
+            ("ease_out_cubic", ease_out_cubic),
+            ("ease_in_out_cubic", ease_in_out_cubic),
+            ("ease_in_quart", ease_in_quart),
+            ("ease_out_quart", ease_out_quart),
+            ("ease_in_out_quart", ease_in_out_quart),
+            ("ease_in_quint", ease_in_quint),
+            ("ease_out_quint", ease_out_quint),
+            ("ease_in_out_quint", ease_in_out_quint),
+            ("ease_in_expo", ease_in_expo),
+            ("ease_out_expo", ease_out_expo),
+            ("ease_in_out_expo", ease_in_out_expo),
+            ("ease_in_circ", ease_in_circ),
+            ("ease_out_circ", ease_out_circ),
+            ("ease_in_out_circ", ease_in_out_circ),
+            ("ease_in_back", ease_in_back),
+            ("ease_out_back", ease_out_back),
+            ("ease_in_out_back", ease_in_out_back),
+            ("ease_in_elastic", ease_in_elastic),
+            ("ease_out_elastic", ease_out_elastic),
+            ("ease_in_out_elastic", ease_in_out_elastic),
+            ("ease_in_bounce", ease_in_bounce),
+            ("ease_out_bounce", ease_out_bounce),
+            ("ease_in_out_bounce", ease_in_out_bounce),
+        ];
+        for (name, f) in funcs {
+            assert_ends(name, f);
+        }
+    }
+
+    #[test]
+    fn combinators() {
+        let f = RateFunc::from(smooth);
+        assert!((f.clone().reversed().apply(0.0) - 1.0).abs() < 1e-6);
+        assert!(f.clone().there_and_back().apply(1.0).abs() < 1e-6);
+        assert!((squish(smooth, 0.25, 0.75).apply(0.5) - 0.5).abs() < 1e-6);
+        assert_eq!(squish(smooth, 0.25, 0.75).apply(0.1), 0.0);
+    }
+}
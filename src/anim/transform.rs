@@ -0,0 +1,235 @@
+use crate::{
+    color::ColorSpace,
+    mobj::{
+        vectorized::{VMobject, Vectorized},
+        MObject,
+    },
+    scene::graph::{Handle, SceneGraph},
+};
+
+use super::{Animation, AnimationSettings};
+
+/// Aligned copies of two mobjects to interpolate between.
+#[derive(Default)]
+struct Morph {
+    start: VMobject,
+    end: VMobject,
+}
+impl Morph {
+    fn begin(&mut self, source: &VMobject, target: &VMobject) {
+        self.start.clone_from(source);
+        self.end.clone_from(target);
+        self.start.align_points(&mut self.end);
+    }
+    fn apply(&self, mobject: &mut VMobject, alpha: f32, color_space: ColorSpace) {
+        mobject.interpolate(&self.start, &self.end, alpha, color_space);
+    }
+}
+
+/// Morphs a [`Vectorized`] mobject into the shape and style of `target`,
+/// which is left untouched and need not be shown.
+///
+/// Once finished, `mobject` has the points and style of `target`, but keeps
+/// its own z-index.
+pub struct Transform<M = VMobject, T = VMobject> {
+    mobject: Handle<M>,
+    target: Handle<T>,
+    morph: Morph,
+    /// Space the colors are mixed in on the way.
+    pub color_space: ColorSpace,
+    pub settings: AnimationSettings,
+}
+impl<M: Vectorized, T: Vectorized> Transform<M, T> {
+    pub fn new(mobject: Handle<M>, target: Handle<T>) -> Self {
+        Self {
+            mobject,
+            target,
+            morph: Morph::default(),
+            color_space: ColorSpace::default(),
+            settings: AnimationSettings::default(),
+        }
+    }
+}
+
+impl<M: Vectorized, T: Vectorized> Animation for Transform<M, T> {
+    fn settings(&self) -> &AnimationSettings {
+        &self.settings
+    }
+
+    fn begin(&mut self, graph: &mut SceneGraph) {
+        self.morph.begin(
+            graph.get(self.mobject).as_ref(),
+            graph.get(self.target).as_ref(),
+        );
+    }
+
+    fn interpolate(&mut self, graph: &mut SceneGraph, alpha: f32) {
+        let alpha = self.settings.alpha(alpha);
+        self.morph.apply(
+            graph.get_mut(self.mobject).as_mut(),
+            alpha,
+            self.color_space,
+        );
+    }
+
+    fn finish(&mut self, graph: &mut SceneGraph) {
+        let target = graph.get(self.target).as_ref().clone();
+        let mobject = graph.get_mut(self.mobject).as_mut();
+        mobject.match_points(&target);
+        mobject.match_style(&target);
+    }
+}
+
+/// Morphs a [`Vectorized`] mobject into `target`, then puts `target` in its place.
+///
+/// `mobject` is removed and restored to its original shape once finished.
+/// Both should share the same transform for the hand-over to be seamless.
+pub struct ReplacementTransform<M = VMobject, T = VMobject> {
+    mobject: Handle<M>,
+    target: Handle<T>,
+    original: VMobject,
+    morph: Morph,
+    /// Space the colors are mixed in on the way.
+    pub color_space: ColorSpace,
+    pub settings: AnimationSettings,
+}
+impl<M: Vectorized, T: Vectorized> ReplacementTransform<M, T> {
+    pub fn new(mobject: Handle<M>, target: Handle<T>) -> Self {
+        Self {
+            mobject,
+            target,
+            original: VMobject::new(),
+            morph: Morph::default(),
+            color_space: ColorSpace::default(),
+            settings: AnimationSettings::default(),
+        }
+    }
+}
+
+impl<M: Vectorized, T: Vectorized> Animation for ReplacementTransform<M, T> {
+    fn settings(&self) -> &AnimationSettings {
+        &self.settings
+    }
+
+    fn begin(&mut self, graph: &mut SceneGraph) {
+        self.original.clone_from(graph.get(self.mobject).as_ref());
+        self.morph
+            .begin(&self.original, graph.get(self.target).as_ref());
+    }
+
+    fn interpolate(&mut self, graph: &mut SceneGraph, alpha: f32) {
+        let alpha = self.settings.alpha(alpha);
+        self.morph.apply(
+            graph.get_mut(self.mobject).as_mut(),
+            alpha,
+            self.color_space,
+        );
+    }
+
+    fn finish(&mut self, graph: &mut SceneGraph) {
+        graph
+            .get_mut(self.mobject)
+            .as_mut()
+            .clone_from(&self.original);
+        graph.replace(self.mobject, self.target);
+    }
+}
+
+/// Cross-fades a [`Vectorized`] mobject into `target` while moving and
+/// stretching both between their bounding boxes, without morphing their
+/// shapes. `target` takes the place of `mobject` once finished.
+pub struct FadeTransform<M = VMobject, T = VMobject> {
+    mobject: Handle<M>,
+    target: Handle<T>,
+    original: VMobject,
+    target_original: VMobject,
+    pub settings: AnimationSettings,
+}
+impl<M: Vectorized, T: Vectorized> FadeTransform<M, T> {
+    pub fn new(mobject: Handle<M>, target: Handle<T>) -> Self {
+        Self {
+            mobject,
+            target,
+            original: VMobject::new(),
+            target_original: VMobject::new(),
+            settings: AnimationSettings::default(),
+        }
+    }
+}
+
+impl<M: Vectorized, T: Vectorized> Animation for FadeTransform<M, T> {
+    fn settings(&self) -> &AnimationSettings {
+        &self.settings
+    }
+
+    fn begin(&mut self, graph: &mut SceneGraph) {
+        self.original.clone_from(graph.get(self.mobject).as_ref());
+        self.target_original
+            .clone_from(graph.get(self.target).as_ref());
+        graph.show(self.target);
+    }
+
+    fn interpolate(&mut self, graph: &mut SceneGraph, alpha: f32) {
+        let alpha = self.settings.alpha(alpha);
+        let boxes = (
+            self.original.bounding_box(),
+            self.target_original.bounding_box(),
+        );
+
+        let fading_out = graph.get_mut(self.mobject).as_mut();
+        fading_out.clone_from(&self.original);
+        fading_out.fade(1.0 - alpha);
+        let fading_in = graph.get_mut(self.target).as_mut();
+        fading_in.clone_from(&self.target_original);
+        fading_in.fade(alpha);
+
+        if let (Some(from), Some(to)) = boxes {
+            let between = (from.0.lerp(to.0, alpha), from.1.lerp(to.1, alpha));
+            graph.get_mut(self.mobject).as_mut().map_box(from, between);
+            graph.get_mut(self.target).as_mut().map_box(to, between);
+        }
+    }
+
+    fn finish(&mut self, graph: &mut SceneGraph) {
+        graph
+            .get_mut(self.mobject)
+            .as_mut()
+            .clone_from(&self.original);
+        graph
+            .get_mut(self.target)
+            .as_mut()
+            .clone_from(&self.target_original);
+        graph.replace(self.mobject, self.target);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use glam::Vec3;
+
+    use crate::color::palette::RED;
+
+    use super::*;
+
+    #[test]
+    fn transform_keeps_z_index() {
+        let mut graph = SceneGraph::new();
+        let mut mobject = VMobject::from_corners(&[Vec3::ZERO, Vec3::X]);
+        mobject.set_z_index(2);
+        let mobject = graph.insert(mobject);
+        let mut target = VMobject::from_corners(&[Vec3::Y, Vec3::ONE, Vec3::X]);
+        target.set_color(RED);
+        target.set_z_index(-1);
+        let target = graph.insert(target);
+
+        let mut transform = Transform::new(mobject, target);
+        transform.begin(&mut graph);
+        transform.interpolate(&mut graph, 0.5);
+        transform.finish(&mut graph);
+
+        let result = graph.get(mobject);
+        assert_eq!(result.points(), graph.get(target).points());
+        assert_eq!(result.stroke_color, RED);
+        assert_eq!(result.z_index(), 2);
+    }
+}
@@ -0,0 +1,508 @@
+use std::{error::Error, fmt, str::FromStr};
+
+use glam::{Vec3, Vec4};
+
+pub mod palette;
+
+/// Saturation or chroma under which a color is considered gray, its hue
+/// left out of interpolation.
+const GRAY: f32 = 1e-3;
+
+/// A color in sRGB with straight alpha, every component from 0 to 1.
+///
+/// Components are stored gamma-encoded, as written in hex codes, and are
+/// converted to other color spaces on demand, e.g. to interpolate between
+/// colors without passing through muddy sRGB midpoints.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Color {
+    pub r: f32,
+    pub g: f32,
+    pub b: f32,
+    pub a: f32,
+}
+
+/// Hue in degrees from 0 to 360, saturation and lightness from 0 to 1.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Hsl {
+    pub h: f32,
+    pub s: f32,
+    pub l: f32,
+}
+
+/// Hue in degrees from 0 to 360, saturation and value from 0 to 1.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Hsv {
+    pub h: f32,
+    pub s: f32,
+    pub v: f32,
+}
+
+/// The perceptual Oklab space: lightness from 0 to 1, and the green-red and
+/// blue-yellow axes, roughly from -0.4 to 0.4.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Oklab {
+    pub l: f32,
+    pub a: f32,
+    pub b: f32,
+}
+
+/// [`Oklab`] in polar form: lightness, chroma and hue in degrees.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Oklch {
+    pub l: f32,
+    pub c: f32,
+    pub h: f32,
+}
+
+/// The space colors are mixed in by [`Color::interpolate`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ColorSpace {
+    /// Mixes the stored components directly, which darkens and desaturates
+    /// midpoints.
+    Srgb,
+    /// Mixes light intensities, as blending does physically.
+    LinearRgb,
+    /// Turns the hue the short way around.
+    Hsl,
+    /// Turns the hue the short way around.
+    Hsv,
+    /// Keeps midpoints perceptually even.
+    #[default]
+    Oklab,
+    /// Keeps chroma up by turning the hue the short way around.
+    Oklch,
+}
+
+/// How a mobject is combined with what is drawn behind it.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum BlendMode {
+    /// Covers what is behind as far as its opacity goes.
+    #[default]
+    Normal,
+    /// Adds its light to what is behind, brightening it.
+    Add,
+    /// Multiplies what is behind by its color, darkening it. Where nothing is
+    /// behind, on a transparent background, the result is black.
+    Multiply,
+    /// Brightens what is behind by the inverse of multiplying the inverted
+    /// colors.
+    Screen,
+}
+impl BlendMode {
+    pub const ALL: [BlendMode; 4] = [
+        BlendMode::Normal,
+        BlendMode::Add,
+        BlendMode::Multiply,
+        BlendMode::Screen,
+    ];
+}
+
+impl Color {
+    pub const TRANSPARENT: Color = Color::rgba(0.0, 0.0, 0.0, 0.0);
+
+    pub const fn rgb(r: f32, g: f32, b: f32) -> Self {
+        Self::rgba(r, g, b, 1.0)
+    }
+    pub const fn rgba(r: f32, g: f32, b: f32, a: f32) -> Self {
+        Self { r, g, b, a }
+    }
+    /// An opaque color from its hex code as a number, e.g. `0x58C4DD`.
+    pub const fn from_u32(hex: u32) -> Self {
+        Self::rgb(
+            ((hex >> 16) & 0xff) as f32 / 255.0,
+            ((hex >> 8) & 0xff) as f32 / 255.0,
+            (hex & 0xff) as f32 / 255.0,
+        )
+    }
+    /// Parses `#rgb`, `#rgba`, `#rrggbb` or `#rrggbbaa`, with or without the
+    /// `#`.
+    pub fn from_hex(hex: &str) -> Result<Self, ParseColorError> {
+        let error = || ParseColorError(hex.to_owned());
+        let digits = hex.strip_prefix('#').unwrap_or(hex);
+        if !digits.chars().all(|c| c.is_ascii_hexdigit()) {
+            return Err(error());
+        }
+        let channel = |i: usize, len: usize| {
+            let value = u8::from_str_radix(&digits[i * len..(i + 1) * len], 16).unwrap();
+            // #abc is short for #aabbcc
+            (if len == 1 { value * 17 } else { value }) as f32 / 255.0
+        };
+        let len = match digits.len() {
+            3 | 4 => 1,
+            6 | 8 => 2,
+            _ => return Err(error()),
+        };
+        let alpha = if digits.len() / len == 4 {
+            channel(3, len)
+        } else {
+            1.0
+        };
+        Ok(Self::rgba(
+            channel(0, len),
+            channel(1, len),
+            channel(2, len),
+            alpha,
+        ))
+    }
+    /// Looks up a color of the [`palette`] by name, ignoring case and
+    /// accepting `grey` for `gray`, e.g. `"blue_c"` or `"Dark Grey"`.
+    /// `"transparent"` gives [`Color::TRANSPARENT`].
+    pub fn from_name(name: &str) -> Option<Self> {
+        let name = name
+            .to_ascii_uppercase()
+            .replace([' ', '-'], "_")
+            .replace("GREY", "GRAY");
+        palette::NAMED
+            .iter()
+            .find(|(n, _)| *n == name)
+            .map(|&(_, color)| color)
+    }
+
+    pub fn with_alpha(self, a: f32) -> Self {
+        Self { a, ..self }
+    }
+
+    // Conversions
+    /// Components with the sRGB transfer function undone, so they are
+    /// proportional to light intensity.
+    pub fn to_linear(self) -> Vec3 {
+        Vec3::new(self.r, self.g, self.b)
+            .to_array()
+            .map(to_linear)
+            .into()
+    }
+    /// An opaque color from linear components, clipped to the sRGB gamut.
+    pub fn from_linear(linear: Vec3) -> Self {
+        let [r, g, b] = linear.to_array().map(|c| from_linear(c.clamp(0.0, 1.0)));
+        Self::rgb(r, g, b)
+    }
+    pub fn to_hsl(self) -> Hsl {
+        let (h, max, min) = hue_max_min(self);
+        let l = (max + min) / 2.0;
+        let d = max - min;
+        let s = if d == 0.0 {
+            0.0
+        } else {
+            d / (1.0 - (2.0 * l - 1.0).abs())
+        };
+        Hsl { h, s, l }
+    }
+    pub fn from_hsl(Hsl { h, s, l }: Hsl) -> Self {
+        let c = (1.0 - (2.0 * l - 1.0).abs()) * s;
+        from_hue_chroma(h, c, l - c / 2.0)
+    }
+    pub fn to_hsv(self) -> Hsv {
+        let (h, max, min) = hue_max_min(self);
+        let s = if max == 0.0 { 0.0 } else { (max - min) / max };
+        Hsv { h, s, v: max }
+    }
+    pub fn from_hsv(Hsv { h, s, v }: Hsv) -> Self {
+        let c = v * s;
+        from_hue_chroma(h, c, v - c)
+    }
+    // Oklab coefficients are kept as published
+    #[allow(clippy::excessive_precision)]
+    pub fn to_oklab(self) -> Oklab {
+        let [r, g, b] = self.to_linear().to_array();
+        let l = (0.4122214708 * r + 0.5363325363 * g + 0.0514459929 * b).cbrt();
+        let m = (0.2119034982 * r + 0.6806995451 * g + 0.1073969566 * b).cbrt();
+        let s = (0.0883024619 * r + 0.2817188376 * g + 0.6299787005 * b).cbrt();
+        Oklab {
+            l: 0.2104542553 * l + 0.7936177850 * m - 0.0040720468 * s,
+            a: 1.9779984951 * l - 2.4285922050 * m + 0.4505937099 * s,
+            b: 0.0259040371 * l + 0.7827717662 * m - 0.8086757660 * s,
+        }
+    }
+    #[allow(clippy::excessive_precision)]
+    pub fn from_oklab(Oklab { l, a, b }: Oklab) -> Self {
+        let l_ = (l + 0.3963377774 * a + 0.2158037573 * b).powi(3);
+        let m = (l - 0.1055613458 * a - 0.0638541728 * b).powi(3);
+        let s = (l - 0.0894841775 * a - 1.2914855480 * b).powi(3);
+        Self::from_linear(Vec3::new(
+            4.0767416621 * l_ - 3.3077115913 * m + 0.2309699292 * s,
+            -1.2684380046 * l_ + 2.6097574011 * m - 0.3413193965 * s,
+            -0.0041960863 * l_ - 0.7034186147 * m + 1.7076147010 * s,
+        ))
+    }
+    pub fn to_oklch(self) -> Oklch {
+        let Oklab { l, a, b } = self.to_oklab();
+        Oklch {
+            l,
+            c: a.hypot(b),
+            h: b.atan2(a).to_degrees().rem_euclid(360.0),
+        }
+    }
+    pub fn from_oklch(Oklch { l, c, h }: Oklch) -> Self {
+        let (sin, cos) = h.to_radians().sin_cos();
+        Self::from_oklab(Oklab {
+            l,
+            a: c * cos,
+            b: c * sin,
+        })
+    }
+
+    /// The color `alpha` of the way from `self` to `other`, mixed in `space`.
+    /// Opacity is always mixed linearly, and a fully transparent color takes
+    /// the color of the other one, so that fading in doesn't go through black.
+    pub fn interpolate(self, other: Color, alpha: f32, space: ColorSpace) -> Color {
+        let (this, other) = match (self.a == 0.0, other.a == 0.0) {
+            (true, false) => (other.with_alpha(0.0), other),
+            (false, true) => (self, self.with_alpha(0.0)),
+            _ => (self, other),
+        };
+        let lerp = |a: f32, b: f32| a + (b - a) * alpha;
+        let color = match space {
+            ColorSpace::Srgb => Self::rgb(
+                lerp(this.r, other.r),
+                lerp(this.g, other.g),
+                lerp(this.b, other.b),
+            ),
+            ColorSpace::LinearRgb => {
+                Self::from_linear(this.to_linear().lerp(other.to_linear(), alpha))
+            }
+            ColorSpace::Hsl => {
+                let (a, b) = (this.to_hsl(), other.to_hsl());
+                Self::from_hsl(Hsl {
+                    h: lerp_hue(a.h, a.s < GRAY, b.h, b.s < GRAY, alpha),
+                    s: lerp(a.s, b.s),
+                    l: lerp(a.l, b.l),
+                })
+            }
+            ColorSpace::Hsv => {
+                let (a, b) = (this.to_hsv(), other.to_hsv());
+                Self::from_hsv(Hsv {
+                    h: lerp_hue(a.h, a.s < GRAY, b.h, b.s < GRAY, alpha),
+                    s: lerp(a.s, b.s),
+                    v: lerp(a.v, b.v),
+                })
+            }
+            ColorSpace::Oklab => {
+                let (a, b) = (this.to_oklab(), other.to_oklab());
+                Self::from_oklab(Oklab {
+                    l: lerp(a.l, b.l),
+                    a: lerp(a.a, b.a),
+                    b: lerp(a.b, b.b),
+                })
+            }
+            ColorSpace::Oklch => {
+                let (a, b) = (this.to_oklch(), other.to_oklch());
+                Self::from_oklch(Oklch {
+                    l: lerp(a.l, b.l),
+                    h: lerp_hue(a.h, a.c < GRAY, b.h, b.c < GRAY, alpha),
+                    c: lerp(a.c, b.c),
+                })
+            }
+        };
+        color.with_alpha(lerp(this.a, other.a))
+    }
+}
+
+impl From<Color> for Vec4 {
+    fn from(color: Color) -> Self {
+        Vec4::new(color.r, color.g, color.b, color.a)
+    }
+}
+impl From<Vec4> for Color {
+    fn from(v: Vec4) -> Self {
+        Self::rgba(v.x, v.y, v.z, v.w)
+    }
+}
+impl From<Vec3> for Color {
+    fn from(v: Vec3) -> Self {
+        Self::rgb(v.x, v.y, v.z)
+    }
+}
+
+/// Parses a hex code or a color name, see [`Color::from_hex`] and
+/// [`Color::from_name`].
+impl FromStr for Color {
+    type Err = ParseColorError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::from_name(s).map_or_else(|| Self::from_hex(s), Ok)
+    }
+}
+
+/// A string is neither a hex code nor the name of a color.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseColorError(String);
+impl fmt::Display for ParseColorError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "invalid color `{}`, expected a hex code like #58C4DD or a name like BLUE_C",
+            self.0
+        )
+    }
+}
+impl Error for ParseColorError {}
+
+fn to_linear(c: f32) -> f32 {
+    if c <= 0.04045 {
+        c / 12.92
+    } else {
+        ((c + 0.055) / 1.055).powf(2.4)
+    }
+}
+fn from_linear(c: f32) -> f32 {
+    if c <= 0.0031308 {
+        c * 12.92
+    } else {
+        1.055 * c.powf(1.0 / 2.4) - 0.055
+    }
+}
+
+/// Hue in degrees, and the largest and smallest of the RGB components.
+fn hue_max_min(Color { r, g, b, .. }: Color) -> (f32, f32, f32) {
+    let max = r.max(g).max(b);
+    let min = r.min(g).min(b);
+    let d = max - min;
+    let h = if d == 0.0 {
+        0.0
+    } else if max == r {
+        (g - b) / d
+    } else if max == g {
+        (b - r) / d + 2.0
+    } else {
+        (r - g) / d + 4.0
+    };
+    ((h * 60.0).rem_euclid(360.0), max, min)
+}
+/// An opaque color of hue `h`, with chroma `c` on top of `m` in every
+/// component.
+fn from_hue_chroma(h: f32, c: f32, m: f32) -> Color {
+    let h = h.rem_euclid(360.0) / 60.0;
+    let x = c * (1.0 - (h % 2.0 - 1.0).abs());
+    let (r, g, b) = match h as u32 {
+        0 => (c, x, 0.0),
+        1 => (x, c, 0.0),
+        2 => (0.0, c, x),
+        3 => (0.0, x, c),
+        4 => (x, 0.0, c),
+        _ => (c, 0.0, x),
+    };
+    Color::rgb(r + m, g + m, b + m)
+}
+/// Turns from hue `a` to hue `b` the short way around. The hue of a gray is
+/// meaningless, so the other one is kept instead.
+fn lerp_hue(a: f32, a_gray: bool, b: f32, b_gray: bool, alpha: f32) -> f32 {
+    let (a, b) = match (a_gray, b_gray) {
+        (true, false) => (b, b),
+        (false, true) => (a, a),
+        _ => (a, b),
+    };
+    let delta = (b - a + 180.0).rem_euclid(360.0) - 180.0;
+    (a + delta * alpha).rem_euclid(360.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{palette::*, *};
+
+    fn bytes(color: Color) -> [u8; 4] {
+        Vec4::from(color)
+            .to_array()
+            .map(|c| (c * 255.0).round() as u8)
+    }
+
+    fn assert_close(a: Color, b: Color) {
+        assert!(
+            Vec4::from(a).abs_diff_eq(Vec4::from(b), 1e-4),
+            "{a:?} != {b:?}"
+        );
+    }
+
+    const SAMPLES: [Color; 8] = [
+        BLUE_C,
+        Color::rgb(1.0, 0.0, 0.0),
+        Color::rgb(0.0, 1.0, 0.0),
+        Color::rgb(0.0, 0.0, 1.0),
+        Color::rgb(1.0, 1.0, 1.0),
+        Color::rgb(0.0, 0.0, 0.0),
+        Color::rgb(0.5, 0.5, 0.5),
+        Color::rgb(0.9, 0.6, 0.1),
+    ];
+
+    #[test]
+    fn hex_forms() {
+        let cases = [
+            ("#58C4DD", [0x58, 0xc4, 0xdd, 0xff]),
+            ("58c4dd", [0x58, 0xc4, 0xdd, 0xff]),
+            ("#58C4DD80", [0x58, 0xc4, 0xdd, 0x80]),
+            ("#abc", [0xaa, 0xbb, 0xcc, 0xff]),
+            ("#abc8", [0xaa, 0xbb, 0xcc, 0x88]),
+            ("F00", [0xff, 0x00, 0x00, 0xff]),
+        ];
+        for (hex, expected) in cases {
+            assert_eq!(bytes(Color::from_hex(hex).unwrap()), expected, "{hex}");
+        }
+        assert_eq!(Color::from_hex("#58C4DD"), Ok(BLUE_C));
+        assert_eq!(Color::from_hex("#aabbcc"), Color::from_hex("#abc"));
+        assert_eq!("blue_c".parse(), Ok(BLUE_C));
+    }
+
+    #[test]
+    fn hex_round_trip() {
+        for color in SAMPLES {
+            let [r, g, b, _] = bytes(color);
+            let hex = format!("#{r:02x}{g:02x}{b:02x}");
+            assert_eq!(bytes(Color::from_hex(&hex).unwrap()), bytes(color));
+            let hex = format!("#{r:02X}{g:02X}{b:02X}40");
+            assert_eq!(
+                bytes(Color::from_hex(&hex).unwrap()),
+                bytes(color.with_alpha(0.25))
+            );
+        }
+    }
+
+    #[test]
+    fn invalid_hex() {
+        for hex in [
+            "",
+            "#",
+            "#12",
+            "#12345",
+            "#1234567",
+            "#123456789",
+            "#ggg",
+            "##123",
+            "#12 3",
+            "#éé",
+        ] {
+            assert_eq!(
+                Color::from_hex(hex),
+                Err(ParseColorError(hex.to_owned())),
+                "{hex}"
+            );
+        }
+        assert!("not a color".parse::<Color>().is_err());
+    }
+
+    #[test]
+    fn oklab_round_trip() {
+        for color in SAMPLES {
+            assert_close(Color::from_oklab(color.to_oklab()), color);
+            assert_close(Color::from_oklch(color.to_oklch()), color);
+        }
+        // reference values from the Oklab paper
+        let white = Color::rgb(1.0, 1.0, 1.0).to_oklab();
+        assert!((white.l - 1.0).abs() < 1e-4 && white.a.abs() < 1e-4 && white.b.abs() < 1e-4);
+        let red = Color::rgb(1.0, 0.0, 0.0).to_oklab();
+        assert!((red.l - 0.62796).abs() < 1e-4);
+        assert!((red.a - 0.22486).abs() < 1e-4);
+        assert!((red.b - 0.12585).abs() < 1e-4);
+    }
+
+    #[test]
+    fn hsl_round_trip() {
+        for color in SAMPLES {
+            assert_close(Color::from_hsl(color.to_hsl()), color);
+            assert_close(Color::from_hsv(color.to_hsv()), color);
+        }
+        let red = Color::rgb(1.0, 0.0, 0.0).to_hsl();
+        assert_eq!((red.h, red.s, red.l), (0.0, 1.0, 0.5));
+        let blue = Color::rgb(0.0, 0.0, 1.0).to_hsl();
+        assert_eq!((blue.h, blue.s, blue.l), (240.0, 1.0, 0.5));
+        let gray = Color::rgb(0.5, 0.5, 0.5).to_hsl();
+        assert_eq!((gray.s, gray.l), (0.0, 0.5));
+    }
+}
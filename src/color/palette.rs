@@ -0,0 +1,172 @@
+//! The standard palette of manim, by the same names.
+
+use super::Color;
+
+pub const WHITE: Color = Color::from_u32(0xFFFFFF);
+pub const GRAY_A: Color = Color::from_u32(0xDDDDDD);
+pub const GRAY_B: Color = Color::from_u32(0xBBBBBB);
+pub const GRAY_C: Color = Color::from_u32(0x888888);
+pub const GRAY_D: Color = Color::from_u32(0x444444);
+pub const GRAY_E: Color = Color::from_u32(0x222222);
+pub const BLACK: Color = Color::from_u32(0x000000);
+
+pub const BLUE_A: Color = Color::from_u32(0xC7E9F1);
+pub const BLUE_B: Color = Color::from_u32(0x9CDCEB);
+pub const BLUE_C: Color = Color::from_u32(0x58C4DD);
+pub const BLUE_D: Color = Color::from_u32(0x29ABCA);
+pub const BLUE_E: Color = Color::from_u32(0x236B8E);
+
+pub const TEAL_A: Color = Color::from_u32(0xACEAD7);
+pub const TEAL_B: Color = Color::from_u32(0x76DDC0);
+pub const TEAL_C: Color = Color::from_u32(0x5CD0B3);
+pub const TEAL_D: Color = Color::from_u32(0x55C1A7);
+pub const TEAL_E: Color = Color::from_u32(0x49A88F);
+
+pub const GREEN_A: Color = Color::from_u32(0xC9E2AE);
+pub const GREEN_B: Color = Color::from_u32(0xA6CF8C);
+pub const GREEN_C: Color = Color::from_u32(0x83C167);
+pub const GREEN_D: Color = Color::from_u32(0x77B05D);
+pub const GREEN_E: Color = Color::from_u32(0x699C52);
+
+pub const YELLOW_A: Color = Color::from_u32(0xFFF1B6);
+pub const YELLOW_B: Color = Color::from_u32(0xFFEA94);
+pub const YELLOW_C: Color = Color::from_u32(0xF7D96F);
+pub const YELLOW_D: Color = Color::from_u32(0xF4D345);
+pub const YELLOW_E: Color = Color::from_u32(0xE8C11C);
+
+pub const GOLD_A: Color = Color::from_u32(0xF7C797);
+pub const GOLD_B: Color = Color::from_u32(0xF9B775);
+pub const GOLD_C: Color = Color::from_u32(0xF0AC5F);
+pub const GOLD_D: Color = Color::from_u32(0xE1A158);
+pub const GOLD_E: Color = Color::from_u32(0xC78D46);
+
+pub const RED_A: Color = Color::from_u32(0xF7A1A3);
+pub const RED_B: Color = Color::from_u32(0xFF8080);
+pub const RED_C: Color = Color::from_u32(0xFC6255);
+pub const RED_D: Color = Color::from_u32(0xE65A4C);
+pub const RED_E: Color = Color::from_u32(0xCF5044);
+
+pub const MAROON_A: Color = Color::from_u32(0xECABC1);
+pub const MAROON_B: Color = Color::from_u32(0xEC92AB);
+pub const MAROON_C: Color = Color::from_u32(0xC55F73);
+pub const MAROON_D: Color = Color::from_u32(0xA24D61);
+pub const MAROON_E: Color = Color::from_u32(0x94424F);
+
+pub const PURPLE_A: Color = Color::from_u32(0xCAA3E8);
+pub const PURPLE_B: Color = Color::from_u32(0xB189C6);
+pub const PURPLE_C: Color = Color::from_u32(0x9A72AC);
+pub const PURPLE_D: Color = Color::from_u32(0x715582);
+pub const PURPLE_E: Color = Color::from_u32(0x644172);
+
+pub const PINK: Color = Color::from_u32(0xD147BD);
+pub const LIGHT_PINK: Color = Color::from_u32(0xDC75CD);
+pub const ORANGE: Color = Color::from_u32(0xFF862F);
+pub const LIGHT_BROWN: Color = Color::from_u32(0xCD853F);
+pub const DARK_BROWN: Color = Color::from_u32(0x8B4513);
+pub const GRAY_BROWN: Color = Color::from_u32(0x736357);
+
+pub const PURE_RED: Color = Color::from_u32(0xFF0000);
+pub const PURE_GREEN: Color = Color::from_u32(0x00FF00);
+pub const PURE_BLUE: Color = Color::from_u32(0x0000FF);
+
+pub const LOGO_WHITE: Color = Color::from_u32(0xECE7E2);
+pub const LOGO_GREEN: Color = Color::from_u32(0x87C2A5);
+pub const LOGO_BLUE: Color = Color::from_u32(0x525893);
+pub const LOGO_RED: Color = Color::from_u32(0xE07A5F);
+pub const LOGO_BLACK: Color = Color::from_u32(0x343434);
+
+// Shorthands for the middle shade of each hue
+pub const LIGHTER_GRAY: Color = GRAY_A;
+pub const LIGHT_GRAY: Color = GRAY_B;
+pub const GRAY: Color = GRAY_C;
+pub const DARK_GRAY: Color = GRAY_D;
+pub const DARKER_GRAY: Color = GRAY_E;
+pub const BLUE: Color = BLUE_C;
+pub const DARK_BLUE: Color = BLUE_E;
+pub const TEAL: Color = TEAL_C;
+pub const GREEN: Color = GREEN_C;
+pub const YELLOW: Color = YELLOW_C;
+pub const GOLD: Color = GOLD_C;
+pub const RED: Color = RED_C;
+pub const MAROON: Color = MAROON_C;
+pub const PURPLE: Color = PURPLE_C;
+
+/// Every color above by name, for [`Color::from_name`].
+pub(super) const NAMED: &[(&str, Color)] = &[
+    ("WHITE", WHITE),
+    ("GRAY_A", GRAY_A),
+    ("GRAY_B", GRAY_B),
+    ("GRAY_C", GRAY_C),
+    ("GRAY_D", GRAY_D),
+    ("GRAY_E", GRAY_E),
+    ("BLACK", BLACK),
+    ("BLUE_A", BLUE_A),
+    ("BLUE_B", BLUE_B),
+    ("BLUE_C", BLUE_C),
+    ("BLUE_D", BLUE_D),
+    ("BLUE_E", BLUE_E),
+    ("TEAL_A", TEAL_A),
+    ("TEAL_B", TEAL_B),
+    ("TEAL_C", TEAL_C),
+    ("TEAL_D", TEAL_D),
+    ("TEAL_E", TEAL_E),
+    ("GREEN_A", GREEN_A),
+    ("GREEN_B", GREEN_B),
+    ("GREEN_C", GREEN_C),
+    ("GREEN_D", GREEN_D),
+    ("GREEN_E", GREEN_E),
+    ("YELLOW_A", YELLOW_A),
+    ("YELLOW_B", YELLOW_B),
+    ("YELLOW_C", YELLOW_C),
+    ("YELLOW_D", YELLOW_D),
+    ("YELLOW_E", YELLOW_E),
+    ("GOLD_A", GOLD_A),
+    ("GOLD_B", GOLD_B),
+    ("GOLD_C", GOLD_C),
+    ("GOLD_D", GOLD_D),
+    ("GOLD_E", GOLD_E),
+    ("RED_A", RED_A),
+    ("RED_B", RED_B),
+    ("RED_C", RED_C),
+    ("RED_D", RED_D),
+    ("RED_E", RED_E),
+    ("MAROON_A", MAROON_A),
+    ("MAROON_B", MAROON_B),
+    ("MAROON_C", MAROON_C),
+    ("MAROON_D", MAROON_D),
+    ("MAROON_E", MAROON_E),
+    ("PURPLE_A", PURPLE_A),
+    ("PURPLE_B", PURPLE_B),
+    ("PURPLE_C", PURPLE_C),
+    ("PURPLE_D", PURPLE_D),
+    ("PURPLE_E", PURPLE_E),
+    ("PINK", PINK),
+    ("LIGHT_PINK", LIGHT_PINK),
+    ("ORANGE", ORANGE),
+    ("LIGHT_BROWN", LIGHT_BROWN),
+    ("DARK_BROWN", DARK_BROWN),
+    ("GRAY_BROWN", GRAY_BROWN),
+    ("PURE_RED", PURE_RED),
+    ("PURE_GREEN", PURE_GREEN),
+    ("PURE_BLUE", PURE_BLUE),
+    ("LOGO_WHITE", LOGO_WHITE),
+    ("LOGO_GREEN", LOGO_GREEN),
+    ("LOGO_BLUE", LOGO_BLUE),
+    ("LOGO_RED", LOGO_RED),
+    ("LOGO_BLACK", LOGO_BLACK),
+    ("LIGHTER_GRAY", LIGHTER_GRAY),
+    ("LIGHT_GRAY", LIGHT_GRAY),
+    ("GRAY", GRAY),
+    ("DARK_GRAY", DARK_GRAY),
+    ("DARKER_GRAY", DARKER_GRAY),
+    ("BLUE", BLUE),
+    ("DARK_BLUE", DARK_BLUE),
+    ("TEAL", TEAL),
+    ("GREEN", GREEN),
+    ("YELLOW", YELLOW),
+    ("GOLD", GOLD),
+    ("RED", RED),
+    ("MAROON", MAROON),
+    ("PURPLE", PURPLE),
+    ("TRANSPARENT", Color::TRANSPARENT),
+];
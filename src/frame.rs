@@ -0,0 +1,68 @@
+use glam::{Vec3, Vec4};
+
+use crate::color::{BlendMode, Color};
+
+/// A single vertex of a [`Mesh`], in world coordinates.
+#[derive(Clone, Copy, Debug)]
+pub struct MeshVertex {
+    pub position: Vec3,
+    pub color: Vec4,
+}
+
+/// A triangle list. Every three indices form one triangle.
+#[derive(Clone, Debug, Default)]
+pub struct Mesh {
+    pub vertices: Vec<MeshVertex>,
+    pub indices: Vec<u32>,
+    /// How the mesh is combined with what is drawn before it. Each pixel is
+    /// blended at most once per mesh, even where its triangles overlap.
+    pub blend_mode: BlendMode,
+}
+impl Mesh {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.indices.is_empty()
+    }
+    /// Appends a triangle fan around the first point of `points`.
+    pub fn push_fan(&mut self, points: &[Vec3], color: Vec4) {
+        if points.len() < 3 {
+            return;
+        }
+        let base = self.vertices.len() as u32;
+        self.vertices.extend(
+            points
+                .iter()
+                .map(|&position| MeshVertex { position, color }),
+        );
+        for i in 1..points.len() as u32 - 1 {
+            self.indices.extend([base, base + i, base + i + 1]);
+        }
+    }
+}
+
+/// Everything that is drawn in one frame of a scene, back to front.
+#[derive(Clone, Debug)]
+pub struct Frame {
+    pub meshes: Vec<Mesh>,
+    /// Size of one output pixel in world units.
+    pub pixel_size: f32,
+    /// Color the output is cleared to before the meshes are drawn. Its alpha
+    /// is kept by output formats that have an alpha channel.
+    pub background: Color,
+}
+impl Frame {
+    pub fn new(pixel_size: f32, background: Color) -> Self {
+        Self {
+            meshes: vec![],
+            pixel_size,
+            background,
+        }
+    }
+    pub fn push(&mut self, mesh: Mesh) {
+        if !mesh.is_empty() {
+            self.meshes.push(mesh);
+        }
+    }
+}
@@ -1,4 +1,7 @@
 pub mod anim;
+pub mod color;
+pub mod frame;
 pub mod mobj;
 pub mod scene;
+pub mod tess;
 pub mod prelude;
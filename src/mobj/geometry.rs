@@ -0,0 +1,395 @@
+use std::{
+    f32::consts::{FRAC_PI_2, TAU},
+    ops::{Deref, DerefMut},
+};
+
+use glam::{vec3, Vec3};
+
+use crate::{
+    color::{BlendMode, Color},
+    frame::Frame,
+};
+
+use super::{vectorized::VMobject, MObject};
+
+/// Radius of a [`Dot`] made with [`Dot::new`].
+pub const DEFAULT_DOT_RADIUS: f32 = 0.08;
+/// Length of each dash of a [`DashedLine`] made with [`DashedLine::new`].
+pub const DEFAULT_DASH_LENGTH: f32 = 0.05;
+/// Fraction of a [`DashedLine`] covered by dashes rather than gaps, unless
+/// set with [`DashedLine::with_dashes`].
+pub const DEFAULT_DASHED_RATIO: f32 = 0.5;
+
+/// Implements what makes a shape usable as the [`VMobject`] it wraps.
+macro_rules! vectorized_shape {
+    ($ty:ident) => {
+        impl Deref for $ty {
+            type Target = VMobject;
+            fn deref(&self) -> &VMobject {
+                &self.0
+            }
+        }
+        impl DerefMut for $ty {
+            fn deref_mut(&mut self) -> &mut VMobject {
+                &mut self.0
+            }
+        }
+        impl AsRef<VMobject> for $ty {
+            fn as_ref(&self) -> &VMobject {
+                &self.0
+            }
+        }
+        impl AsMut<VMobject> for $ty {
+            fn as_mut(&mut self) -> &mut VMobject {
+                &mut self.0
+            }
+        }
+        impl From<$ty> for VMobject {
+            fn from(shape: $ty) -> Self {
+                shape.0
+            }
+        }
+
+        impl MObject for $ty {
+            fn draw(&self, frame: &mut Frame) {
+                self.0.draw(frame)
+            }
+            fn points(&self) -> &[Vec3] {
+                self.0.points()
+            }
+            fn points_mut(&mut self) -> &mut [Vec3] {
+                self.0.points_mut()
+            }
+            fn set_color(&mut self, color: Color) {
+                self.0.set_color(color)
+            }
+            fn set_opacity(&mut self, opacity: f32) {
+                self.0.set_opacity(opacity)
+            }
+            fn set_blend_mode(&mut self, blend_mode: BlendMode) {
+                self.0.set_blend_mode(blend_mode)
+            }
+            fn z_index(&self) -> i32 {
+                self.0.z_index()
+            }
+            fn set_z_index(&mut self, z_index: i32) {
+                self.0.set_z_index(z_index)
+            }
+        }
+    };
+}
+
+/// An arc of a circle around the origin, turning counterclockwise from
+/// `start_angle` for `angle` radians, or clockwise if `angle` is negative.
+#[derive(Clone, Debug)]
+pub struct Arc(VMobject);
+impl Arc {
+    pub fn new(radius: f32, start_angle: f32, angle: f32) -> Self {
+        let mut vmobj = VMobject::new();
+        vmobj.set_points(arc_points(start_angle, angle, |p| p * radius));
+        Self(vmobj)
+    }
+}
+vectorized_shape!(Arc);
+
+/// A circle around the origin, starting at angle 0.
+#[derive(Clone, Debug)]
+pub struct Circle(VMobject);
+impl Circle {
+    pub fn new(radius: f32) -> Self {
+        Self(Ellipse::new(2.0 * radius, 2.0 * radius).0)
+    }
+}
+vectorized_shape!(Circle);
+
+/// An ellipse around the origin, with its axes along x and y.
+#[derive(Clone, Debug)]
+pub struct Ellipse(VMobject);
+impl Ellipse {
+    pub fn new(width: f32, height: f32) -> Self {
+        let radii = vec3(width / 2.0, height / 2.0, 0.0);
+        let mut vmobj = VMobject::new();
+        vmobj.set_points(arc_points(0.0, TAU, |p| p * radii));
+        Self(vmobj)
+    }
+}
+vectorized_shape!(Ellipse);
+
+/// A filled slice of a disk around the origin, bounded by the arc of
+/// [`Arc::new`] and the radii to both of its ends.
+#[derive(Clone, Debug)]
+pub struct Sector(VMobject);
+impl Sector {
+    pub fn new(radius: f32, start_angle: f32, angle: f32) -> Self {
+        let arc = arc_points(start_angle, angle, |p| p * radius);
+        let mut vmobj = VMobject::new();
+        vmobj.start_new_path(Vec3::ZERO);
+        vmobj.add_line_to(arc[0]);
+        for curve in arc.chunks(4) {
+            vmobj.add_cubic_bezier_curve_to(curve[1], curve[2], curve[3]);
+        }
+        vmobj.close_path();
+        vmobj.fill_color = vmobj.stroke_color;
+        Self(vmobj)
+    }
+}
+vectorized_shape!(Sector);
+
+/// A filled ring around the origin.
+#[derive(Clone, Debug)]
+pub struct Annulus(VMobject);
+impl Annulus {
+    pub fn new(inner_radius: f32, outer_radius: f32) -> Self {
+        let mut points = arc_points(0.0, TAU, |p| p * outer_radius);
+        // The inner circle runs the other way so it cuts a hole under either
+        // fill rule.
+        points.extend(arc_points(0.0, -TAU, |p| p * inner_radius));
+        let mut vmobj = VMobject::new();
+        vmobj.set_points(points);
+        vmobj.fill_color = vmobj.stroke_color;
+        Self(vmobj)
+    }
+}
+vectorized_shape!(Annulus);
+
+/// A straight line segment.
+#[derive(Clone, Debug)]
+pub struct Line(VMobject);
+impl Line {
+    pub fn new(start: Vec3, end: Vec3) -> Self {
+        Self(VMobject::from_corners(&[start, end]))
+    }
+}
+vectorized_shape!(Line);
+
+/// A line segment made of evenly spaced dashes, with a whole dash at either
+/// end.
+#[derive(Clone, Debug)]
+pub struct DashedLine(VMobject);
+impl DashedLine {
+    pub fn new(start: Vec3, end: Vec3) -> Self {
+        Self::with_dashes(start, end, DEFAULT_DASH_LENGTH, DEFAULT_DASHED_RATIO)
+    }
+    /// Dashes of about `dash_length`, covering `dashed_ratio` of the line.
+    /// Both are adjusted slightly so that the dashes fit the line exactly.
+    pub fn with_dashes(start: Vec3, end: Vec3, dash_length: f32, dashed_ratio: f32) -> Self {
+        assert!(
+            dash_length > 0.0 && dashed_ratio > 0.0 && dashed_ratio <= 1.0,
+            "dashes must have a positive length and ratio of at most 1"
+        );
+        let length = start.distance(end);
+        let gap_length = dash_length * (1.0 / dashed_ratio - 1.0);
+        let num_dashes = ((length + gap_length) / (dash_length + gap_length))
+            .round()
+            .max(1.0);
+        // n dashes and n - 1 gaps
+        let period = 1.0 / (num_dashes - 1.0 + dashed_ratio);
+
+        let mut vmobj = VMobject::new();
+        for i in 0..num_dashes as usize {
+            let a = i as f32 * period;
+            vmobj.start_new_path(start.lerp(end, a));
+            vmobj.add_line_to(start.lerp(end, a + dashed_ratio * period));
+        }
+        Self(vmobj)
+    }
+}
+vectorized_shape!(DashedLine);
+
+/// Shape of the tip of an [`Arrow`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum TipShape {
+    #[default]
+    Triangle,
+    /// A triangle with a notch in its base.
+    Stealth,
+    Circle,
+    Square,
+}
+
+/// The filled tip of an [`Arrow`] or [`DoubleArrow`].
+#[derive(Clone, Copy, Debug)]
+pub struct ArrowTip {
+    pub shape: TipShape,
+    /// Extent along the arrow.
+    pub length: f32,
+    /// Extent across the arrow.
+    pub width: f32,
+}
+impl Default for ArrowTip {
+    fn default() -> Self {
+        Self {
+            shape: TipShape::default(),
+            length: 0.35,
+            width: 0.35,
+        }
+    }
+}
+
+/// A line segment with a tip at its end.
+///
+/// Tips are shrunk on arrows too short to fit them, so that they take up at
+/// most half the length.
+#[derive(Clone, Debug)]
+pub struct Arrow(VMobject);
+impl Arrow {
+    pub fn new(start: Vec3, end: Vec3) -> Self {
+        Self::with_tip(start, end, ArrowTip::default())
+    }
+    pub fn with_tip(start: Vec3, end: Vec3, tip: ArrowTip) -> Self {
+        Self(arrow(start, end, tip, false))
+    }
+}
+vectorized_shape!(Arrow);
+
+/// A line segment with a tip at both ends.
+#[derive(Clone, Debug)]
+pub struct DoubleArrow(VMobject);
+impl DoubleArrow {
+    pub fn new(start: Vec3, end: Vec3) -> Self {
+        Self::with_tip(start, end, ArrowTip::default())
+    }
+    pub fn with_tip(start: Vec3, end: Vec3, tip: ArrowTip) -> Self {
+        Self(arrow(start, end, tip, true))
+    }
+}
+vectorized_shape!(DoubleArrow);
+
+/// A small filled circle marking a point.
+#[derive(Clone, Debug)]
+pub struct Dot(VMobject);
+impl Dot {
+    pub fn new(point: Vec3) -> Self {
+        Self::with_radius(point, DEFAULT_DOT_RADIUS)
+    }
+    pub fn with_radius(point: Vec3, radius: f32) -> Self {
+        let mut vmobj = VMobject::new();
+        vmobj.set_points(arc_points(0.0, TAU, |p| point + p * radius));
+        vmobj.fill_color = vmobj.stroke_color;
+        Self(vmobj)
+    }
+}
+vectorized_shape!(Dot);
+
+/// A square around the origin, with its sides along x and y.
+#[derive(Clone, Debug)]
+pub struct Square(VMobject);
+impl Square {
+    pub fn new(side_length: f32) -> Self {
+        let h = side_length / 2.0;
+        let mut vmobj = VMobject::from_corners(&[
+            vec3(h, h, 0.0),
+            vec3(-h, h, 0.0),
+            vec3(-h, -h, 0.0),
+            vec3(h, -h, 0.0),
+        ]);
+        vmobj.close_path();
+        Self(vmobj)
+    }
+}
+vectorized_shape!(Square);
+
+/// Cubic Bezier curves following the unit circle from `start_angle` for
+/// `angle` radians, each mapped through `map`.
+///
+/// Each curve spans at most a quarter turn, with handles placed so that its
+/// ends and midpoint lie on the circle, which keeps the radius within 0.03%
+/// everywhere else.
+fn arc_points(start_angle: f32, angle: f32, map: impl Fn(Vec3) -> Vec3) -> Vec<Vec3> {
+    let n = ((angle.abs() / FRAC_PI_2).ceil() as usize).max(1);
+    let step = angle / n as f32;
+    let handle_length = 4.0 / 3.0 * (step / 4.0).tan();
+
+    let point = |a: f32| vec3(a.cos(), a.sin(), 0.0);
+    let tangent = |a: f32| vec3(-a.sin(), a.cos(), 0.0);
+    let mut points: Vec<_> = (0..n)
+        .flat_map(|i| {
+            let a0 = start_angle + step * i as f32;
+            let a1 = start_angle + step * (i + 1) as f32;
+            [
+                point(a0),
+                point(a0) + handle_length * tangent(a0),
+                point(a1) - handle_length * tangent(a1),
+                point(a1),
+            ]
+        })
+        .map(map)
+        .collect();
+    // full turns end exactly where they start, so they are drawn closed
+    if angle.abs() >= TAU {
+        let last = points.len() - 1;
+        points[last] = points[0];
+    }
+    points
+}
+
+fn arrow(start: Vec3, end: Vec3, tip: ArrowTip, double: bool) -> VMobject {
+    let length = start.distance(end);
+    if length == 0.0 {
+        return VMobject::from_corners(&[start, end]);
+    }
+    let direction = (end - start) / length;
+    let scale = (length / 2.0 / tip.length).min(1.0);
+    let tip = ArrowTip {
+        length: tip.length * scale,
+        width: tip.width * scale,
+        ..tip
+    };
+
+    let mut tips = VMobject::new();
+    let shaft_end = add_tip(&mut tips, end, direction, tip);
+    let shaft_start = if double {
+        add_tip(&mut tips, start, -direction, tip)
+    } else {
+        start
+    };
+
+    let mut vmobj = VMobject::from_corners(&[shaft_start, shaft_end]);
+    vmobj.append_vectorized_mobject(&tips);
+    vmobj.fill_color = vmobj.stroke_color;
+    vmobj
+}
+
+/// Adds a tip pointing at `point` along `direction` to `vmobj`, and returns
+/// where the shaft should end to meet it.
+fn add_tip(vmobj: &mut VMobject, point: Vec3, direction: Vec3, tip: ArrowTip) -> Vec3 {
+    let across = Vec3::Z.cross(direction).try_normalize().unwrap_or(Vec3::Y);
+    let (along, across) = (direction * tip.length, across * tip.width / 2.0);
+    let base = point - along;
+
+    match tip.shape {
+        TipShape::Triangle => {
+            add_polygon(vmobj, &[point, base + across, base - across]);
+            base
+        }
+        TipShape::Stealth => {
+            let notch = base + 0.3 * along;
+            add_polygon(vmobj, &[point, base + across, notch, base - across]);
+            notch
+        }
+        TipShape::Circle => {
+            let center = point - along / 2.0;
+            let points = arc_points(0.0, TAU, |p| center + p.x * along / 2.0 + p.y * across);
+            vmobj.start_new_path(points[0]);
+            for curve in points.chunks(4) {
+                vmobj.add_cubic_bezier_curve_to(curve[1], curve[2], curve[3]);
+            }
+            base
+        }
+        TipShape::Square => {
+            add_polygon(
+                vmobj,
+                &[point + across, base + across, base - across, point - across],
+            );
+            base
+        }
+    }
+}
+
+fn add_polygon(vmobj: &mut VMobject, corners: &[Vec3]) {
+    vmobj.start_new_path(corners[0]);
+    for &corner in &corners[1..] {
+        vmobj.add_line_to(corner);
+    }
+    vmobj.close_path();
+}
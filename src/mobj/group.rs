@@ -0,0 +1,96 @@
+use glam::Vec3;
+
+use crate::{
+    color::{BlendMode, Color},
+    frame::Frame,
+};
+
+use super::MObject;
+
+/// A mobject made of other mobjects, so they can be positioned and styled
+/// as one.
+///
+/// Submobjects are drawn back to front in the order they were added, with
+/// those of a higher z-index in front.
+#[derive(Default)]
+pub struct Group {
+    submobjects: Vec<Box<dyn MObject>>,
+    z_index: i32,
+}
+impl Group {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds `mobject` in front of the others and returns its index.
+    pub fn push<M: MObject>(&mut self, mobject: M) -> usize {
+        self.submobjects.push(Box::new(mobject));
+        self.submobjects.len() - 1
+    }
+    /// The submobject at `index`, if it exists and is an `M`.
+    pub fn get<M: MObject>(&self, index: usize) -> Option<&M> {
+        self.submobjects
+            .get(index)?
+            .as_ref()
+            .as_any()
+            .downcast_ref()
+    }
+    pub fn get_mut<M: MObject>(&mut self, index: usize) -> Option<&mut M> {
+        self.submobjects
+            .get_mut(index)?
+            .as_mut()
+            .as_any_mut()
+            .downcast_mut()
+    }
+    pub fn len(&self) -> usize {
+        self.submobjects.len()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.submobjects.is_empty()
+    }
+}
+
+impl MObject for Group {
+    fn draw(&self, frame: &mut Frame) {
+        let mut submobjects: Vec<_> = self.submobjects.iter().collect();
+        submobjects.sort_by_key(|submobject| submobject.z_index());
+        for submobject in submobjects {
+            submobject.draw(frame);
+        }
+    }
+
+    fn points(&self) -> &[Vec3] {
+        &[]
+    }
+    fn points_mut(&mut self) -> &mut [Vec3] {
+        &mut []
+    }
+    fn submobjects(&self) -> &[Box<dyn MObject>] {
+        &self.submobjects
+    }
+    fn submobjects_mut(&mut self) -> &mut [Box<dyn MObject>] {
+        &mut self.submobjects
+    }
+
+    fn set_color(&mut self, color: Color) {
+        for submobject in &mut self.submobjects {
+            submobject.set_color(color);
+        }
+    }
+    fn set_opacity(&mut self, opacity: f32) {
+        for submobject in &mut self.submobjects {
+            submobject.set_opacity(opacity);
+        }
+    }
+    fn set_blend_mode(&mut self, blend_mode: BlendMode) {
+        for submobject in &mut self.submobjects {
+            submobject.set_blend_mode(blend_mode);
+        }
+    }
+    fn z_index(&self) -> i32 {
+        self.z_index
+    }
+    fn set_z_index(&mut self, z_index: i32) {
+        self.z_index = z_index;
+    }
+}
@@ -0,0 +1,379 @@
+use glam::{const_vec3, vec3, Vec2, Vec3};
+
+use crate::scene::SceneConfig;
+
+use super::MObject;
+
+pub const ORIGIN: Vec3 = Vec3::ZERO;
+pub const UP: Vec3 = Vec3::Y;
+pub const DOWN: Vec3 = const_vec3!([0.0, -1.0, 0.0]);
+pub const RIGHT: Vec3 = Vec3::X;
+pub const LEFT: Vec3 = const_vec3!([-1.0, 0.0, 0.0]);
+/// Towards the viewer.
+pub const OUT: Vec3 = Vec3::Z;
+/// Away from the viewer.
+pub const IN: Vec3 = const_vec3!([0.0, 0.0, -1.0]);
+pub const UL: Vec3 = const_vec3!([-1.0, 1.0, 0.0]);
+pub const UR: Vec3 = const_vec3!([1.0, 1.0, 0.0]);
+pub const DL: Vec3 = const_vec3!([-1.0, -1.0, 0.0]);
+pub const DR: Vec3 = const_vec3!([1.0, -1.0, 0.0]);
+
+pub const SMALL_BUFF: f32 = 0.1;
+pub const MED_SMALL_BUFF: f32 = 0.25;
+pub const MED_LARGE_BUFF: f32 = 0.5;
+pub const LARGE_BUFF: f32 = 1.0;
+/// Usual gap between mobjects placed next to each other.
+pub const DEFAULT_MOBJECT_BUFF: f32 = MED_SMALL_BUFF;
+/// Usual gap between a mobject and the edge of the frame.
+pub const DEFAULT_EDGE_BUFF: f32 = MED_LARGE_BUFF;
+
+/// Something to place mobjects relative to: a point, a box given by its
+/// minimum and maximum corners, or another mobject.
+pub trait Anchor {
+    fn bounds(&self) -> (Vec3, Vec3);
+}
+impl Anchor for Vec3 {
+    fn bounds(&self) -> (Vec3, Vec3) {
+        (*self, *self)
+    }
+}
+impl Anchor for (Vec3, Vec3) {
+    fn bounds(&self) -> (Vec3, Vec3) {
+        *self
+    }
+}
+/// The bounding box of the mobject, or the origin if it has no points.
+impl<M: MObject + ?Sized> Anchor for &M {
+    fn bounds(&self) -> (Vec3, Vec3) {
+        self.bounding_box().unwrap_or((Vec3::ZERO, Vec3::ZERO))
+    }
+}
+
+/// Where submobjects go in [`Layout::arrange_in_grid`].
+#[derive(Clone, Copy, Debug)]
+pub struct Grid {
+    /// Number of rows, or enough to fit all submobjects if `None`.
+    pub rows: Option<usize>,
+    /// Number of columns. If neither this nor `rows` is given, the grid is
+    /// made about as wide as it is tall in cells.
+    pub cols: Option<usize>,
+    /// Horizontal and vertical gap between cells.
+    pub buff: Vec2,
+    /// Where each submobject sits within its cell, e.g. [`UL`] for its top
+    /// left corner. The default centers it.
+    pub cell_alignment: Vec3,
+    /// Fill the grid column by column instead of row by row.
+    pub column_major: bool,
+}
+impl Default for Grid {
+    fn default() -> Self {
+        Self {
+            rows: None,
+            cols: None,
+            buff: Vec2::splat(MED_SMALL_BUFF),
+            cell_alignment: ORIGIN,
+            column_major: false,
+        }
+    }
+}
+
+/// Placement of mobjects relative to points, other mobjects and the frame.
+///
+/// Directions are given like [`UP`] or [`DR`]. Each axis is only looked at
+/// for its sign, so [`DR`] means the bottom right corner of a box rather than
+/// a point on a diagonal.
+///
+/// Positions are in the mobject's own coordinates. For a mobject in a
+/// [`SceneGraph`](crate::scene::graph::SceneGraph) under a transform, lay it
+/// out inside [`SceneGraph::in_world_space`](crate::scene::graph::SceneGraph::in_world_space),
+/// against the [`SceneGraph::bounding_box`](crate::scene::graph::SceneGraph::bounding_box)
+/// of other mobjects.
+pub trait Layout: MObject {
+    /// The point of the bounding box in `direction` from its center, which is
+    /// the center of an edge for [`UP`] or a corner for [`UL`].
+    fn critical_point(&self, direction: Vec3) -> Vec3 {
+        critical_point(self.bounding_box().unwrap_or_default(), direction)
+    }
+    fn width(&self) -> f32 {
+        self.bounding_box().map_or(0.0, |(min, max)| max.x - min.x)
+    }
+    fn height(&self) -> f32 {
+        self.bounding_box().map_or(0.0, |(min, max)| max.y - min.y)
+    }
+
+    /// Moves the center onto the center of `target`.
+    fn move_to(&mut self, target: impl Anchor) {
+        let (min, max) = target.bounds();
+        self.shift((min + max) / 2.0 - self.center());
+    }
+    /// Places this mobject beside `target` in `direction`, with a gap of
+    /// `buff` between them, and centered with it along the other axes.
+    fn next_to(&mut self, target: impl Anchor, direction: Vec3, buff: f32) {
+        let target_point = critical_point(target.bounds(), direction);
+        let point = self.critical_point(-direction);
+        self.shift(target_point - point + buff * direction);
+    }
+    /// Lines up the edges in `direction` of this mobject and `target`,
+    /// leaving the other axes alone. [`UL`] aligns both the top and the left
+    /// edges.
+    fn align_to(&mut self, target: impl Anchor, direction: Vec3) {
+        let target_point = critical_point(target.bounds(), direction);
+        let point = self.critical_point(direction);
+        self.shift((target_point - point) * sign(direction).abs());
+    }
+    /// Moves this mobject against the edge of the frame in `edge`, `buff`
+    /// away from it, leaving the other axes alone.
+    fn to_edge(&mut self, edge: Vec3, buff: f32, frame: &SceneConfig) {
+        let target_point = critical_point(frame.frame_box(), edge);
+        let point = self.critical_point(edge);
+        let edge = sign(edge);
+        self.shift((target_point - point - buff * edge) * edge.abs());
+    }
+    /// Moves this mobject into the corner of the frame in `corner`, such as
+    /// [`UL`], `buff` away from both edges.
+    fn to_corner(&mut self, corner: Vec3, buff: f32, frame: &SceneConfig) {
+        self.to_edge(corner, buff, frame);
+    }
+
+    /// Places the submobjects one after another in `direction`, `buff`
+    /// apart, each centered with the previous one along the other axes. The
+    /// group stays centered where it was.
+    fn arrange(&mut self, direction: Vec3, buff: f32) {
+        let center = self.center();
+        let submobjects = self.submobjects_mut();
+        for i in 1..submobjects.len() {
+            if let Some(previous) = submobjects[i - 1].bounding_box() {
+                submobjects[i].next_to(previous, direction, buff);
+            }
+        }
+        self.move_to(center);
+    }
+    /// Places the submobjects in the cells of a grid, filled from the top
+    /// left. Each column is as wide as its widest submobject and each row as
+    /// tall as its tallest. The group stays centered where it was.
+    ///
+    /// Panics if `grid` has zero rows or columns, or fewer cells than there
+    /// are submobjects.
+    fn arrange_in_grid(&mut self, grid: Grid) {
+        assert!(
+            grid.rows != Some(0) && grid.cols != Some(0),
+            "grid needs at least one row and column"
+        );
+        let center = self.center();
+        let submobjects = self.submobjects_mut();
+        let n = submobjects.len();
+        if n == 0 {
+            return;
+        }
+        let (rows, cols) = match (grid.rows, grid.cols) {
+            (Some(rows), Some(cols)) => {
+                assert!(rows * cols >= n, "grid too small for all submobjects");
+                (rows, cols)
+            }
+            (Some(rows), None) => (rows, n.div_ceil(rows)),
+            (None, Some(cols)) => (n.div_ceil(cols), cols),
+            (None, None) => {
+                let cols = (n as f32).sqrt().ceil() as usize;
+                (n.div_ceil(cols), cols)
+            }
+        };
+        let cell = |i: usize| match grid.column_major {
+            false => (i / cols, i % cols),
+            true => (i % rows, i / rows),
+        };
+
+        let mut col_widths = vec![0.0f32; cols];
+        let mut row_heights = vec![0.0f32; rows];
+        for (i, submobject) in submobjects.iter().enumerate() {
+            let (row, col) = cell(i);
+            col_widths[col] = col_widths[col].max(submobject.width());
+            row_heights[row] = row_heights[row].max(submobject.height());
+        }
+        // left and top edges of each cell
+        let lefts = offsets(&col_widths, grid.buff.x);
+        let tops: Vec<_> = offsets(&row_heights, grid.buff.y)
+            .into_iter()
+            .map(|y| -y)
+            .collect();
+
+        for (i, submobject) in submobjects.iter_mut().enumerate() {
+            let (row, col) = cell(i);
+            let cell_box = (
+                vec3(lefts[col], tops[row] - row_heights[row], 0.0),
+                vec3(lefts[col] + col_widths[col], tops[row], 0.0),
+            );
+            let target_point = critical_point(cell_box, grid.cell_alignment);
+            let point = submobject.critical_point(grid.cell_alignment);
+            submobject.shift((target_point - point) * vec3(1.0, 1.0, 0.0));
+        }
+        self.move_to(center);
+    }
+}
+impl<M: MObject + ?Sized> Layout for M {}
+
+fn critical_point((min, max): (Vec3, Vec3), direction: Vec3) -> Vec3 {
+    let center = (min + max) / 2.0;
+    center + (max - min) / 2.0 * sign(direction)
+}
+
+/// Start of each of `sizes` when laid end to end, `buff` apart.
+fn offsets(sizes: &[f32], buff: f32) -> Vec<f32> {
+    sizes
+        .iter()
+        .scan(0.0, |start, size| {
+            let current = *start;
+            *start += size + buff;
+            Some(current)
+        })
+        .collect()
+}
+
+/// Like [`Vec3::signum`], but 0 for components that are 0.
+fn sign(v: Vec3) -> Vec3 {
+    Vec3::select(v.cmpeq(Vec3::ZERO), Vec3::ZERO, v.signum())
+}
+
+#[cfg(test)]
+mod tests {
+    use glam::vec2;
+
+    use crate::mobj::{group::Group, vectorized::VMobject};
+
+    use super::*;
+
+    /// A `width` by `height` rectangle with its bottom left corner at the
+    /// origin.
+    fn rect(width: f32, height: f32) -> VMobject {
+        VMobject::from_corners(&[
+            Vec3::ZERO,
+            vec3(width, 0.0, 0.0),
+            vec3(width, height, 0.0),
+            vec3(0.0, height, 0.0),
+            Vec3::ZERO,
+        ])
+    }
+
+    fn group(sizes: &[(f32, f32)]) -> Group {
+        let mut group = Group::new();
+        for &(width, height) in sizes {
+            group.push(rect(width, height));
+        }
+        group
+    }
+
+    fn assert_box(actual: Option<(Vec3, Vec3)>, min: Vec3, max: Vec3) {
+        let (actual_min, actual_max) = actual.unwrap();
+        assert!(
+            actual_min.abs_diff_eq(min, 1e-5) && actual_max.abs_diff_eq(max, 1e-5),
+            "{actual_min}..{actual_max} != {min}..{max}"
+        );
+    }
+
+    #[test]
+    fn next_to() {
+        let target = (Vec3::ZERO, vec3(2.0, 2.0, 0.0));
+
+        let mut mobj = rect(1.0, 1.0);
+        mobj.next_to(target, RIGHT, 0.5);
+        assert_box(
+            mobj.bounding_box(),
+            vec3(2.5, 0.5, 0.0),
+            vec3(3.5, 1.5, 0.0),
+        );
+
+        mobj.next_to(target, DOWN, SMALL_BUFF);
+        assert_box(
+            mobj.bounding_box(),
+            vec3(0.5, -1.1, 0.0),
+            vec3(1.5, -0.1, 0.0),
+        );
+
+        // a corner direction places it diagonally, `buff` away on each axis
+        mobj.next_to(target, UR, 0.5);
+        assert_box(
+            mobj.bounding_box(),
+            vec3(2.5, 2.5, 0.0),
+            vec3(3.5, 3.5, 0.0),
+        );
+    }
+
+    #[test]
+    fn arrange() {
+        let mut row = group(&[(1.0, 1.0), (2.0, 2.0)]);
+        row.arrange(RIGHT, MED_SMALL_BUFF);
+
+        // laid out from (0, 0), then moved back to the old center (1, 1)
+        let shift = vec3(-0.625, 0.5, 0.0);
+        let subs = row.submobjects();
+        assert_box(subs[0].bounding_box(), shift, vec3(1.0, 1.0, 0.0) + shift);
+        assert_box(
+            subs[1].bounding_box(),
+            vec3(1.25, -0.5, 0.0) + shift,
+            vec3(3.25, 1.5, 0.0) + shift,
+        );
+        assert!(row.center().abs_diff_eq(vec3(1.0, 1.0, 0.0), 1e-5));
+    }
+
+    #[test]
+    fn arrange_in_grid() {
+        let mut grid = group(&[(1.0, 1.0), (2.0, 1.0), (1.0, 2.0), (1.0, 1.0)]);
+        grid.arrange_in_grid(Grid {
+            buff: vec2(0.5, 0.25),
+            cell_alignment: UL,
+            ..Default::default()
+        });
+
+        // columns are 1 and 2 wide, rows 1 and 2 tall; the layout spans
+        // (0, -3.25) to (3.5, 0) before moving back to the old center (1, 1)
+        let shift = vec3(-0.75, 2.625, 0.0);
+        let expected = [
+            ((0.0, -1.0), (1.0, 0.0)),
+            ((1.5, -1.0), (3.5, 0.0)),
+            ((0.0, -3.25), (1.0, -1.25)),
+            ((1.5, -2.25), (2.5, -1.25)),
+        ];
+        for (sub, ((x0, y0), (x1, y1))) in grid.submobjects().iter().zip(expected) {
+            assert_box(
+                sub.bounding_box(),
+                vec3(x0, y0, 0.0) + shift,
+                vec3(x1, y1, 0.0) + shift,
+            );
+        }
+    }
+
+    #[test]
+    fn arrange_in_grid_column_major() {
+        let mut grid = group(&[(1.0, 1.0); 3]);
+        grid.arrange_in_grid(Grid {
+            rows: Some(2),
+            buff: Vec2::ZERO,
+            column_major: true,
+            ..Default::default()
+        });
+        // two rows, so the third cell starts the second column
+        let (min, _) = grid.bounding_box().unwrap();
+        let corner = |i: usize| grid.submobjects()[i].bounding_box().unwrap().0 - min;
+        assert!(corner(1).abs_diff_eq(Vec3::ZERO, 1e-5));
+        assert!(corner(0).abs_diff_eq(vec3(0.0, 1.0, 0.0), 1e-5));
+        assert!(corner(2).abs_diff_eq(vec3(1.0, 1.0, 0.0), 1e-5));
+    }
+
+    #[test]
+    #[should_panic(expected = "at least one row and column")]
+    fn arrange_in_grid_zero_rows() {
+        group(&[(1.0, 1.0); 2]).arrange_in_grid(Grid {
+            rows: Some(0),
+            cols: Some(3),
+            ..Default::default()
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "at least one row and column")]
+    fn arrange_in_grid_zero_cols() {
+        group(&[(1.0, 1.0); 2]).arrange_in_grid(Grid {
+            cols: Some(0),
+            ..Default::default()
+        });
+    }
+}
@@ -1,6 +1,124 @@
+use std::any::Any;
+
+use glam::{Mat4, Quat, Vec3};
+
+use crate::{
+    color::{BlendMode, Color},
+    frame::Frame,
+};
+
+pub mod geometry;
+pub mod group;
+pub mod layout;
 pub mod polygon;
 pub mod vectorized;
 
-pub trait MObject {
-    
-}
\ No newline at end of file
+/// Anything that can be shown in a scene.
+///
+/// Implementors only provide their own points and style. Positioning is done
+/// by moving the points of the mobject and all of its submobjects, so every
+/// mobject can be moved, scaled and rotated the same way.
+pub trait MObject: AsAny {
+    /// Appends the geometry of this mobject and its submobjects to `frame`.
+    fn draw(&self, frame: &mut Frame);
+
+    // Points
+    /// The points defining the shape of this mobject, not including those of
+    /// its submobjects.
+    fn points(&self) -> &[Vec3];
+    fn points_mut(&mut self) -> &mut [Vec3];
+    fn submobjects(&self) -> &[Box<dyn MObject>] {
+        &[]
+    }
+    fn submobjects_mut(&mut self) -> &mut [Box<dyn MObject>] {
+        &mut []
+    }
+
+    // Style
+    /// Sets the color of this mobject and its submobjects, opacity included.
+    fn set_color(&mut self, color: Color);
+    /// Sets the opacity of this mobject and its submobjects.
+    fn set_opacity(&mut self, opacity: f32);
+    /// Sets how this mobject and its submobjects are combined with what is
+    /// behind them.
+    fn set_blend_mode(&mut self, blend_mode: BlendMode);
+    /// Mobjects with a higher z-index are drawn in front of their siblings,
+    /// regardless of the order they were added in.
+    fn z_index(&self) -> i32;
+    fn set_z_index(&mut self, z_index: i32);
+
+    // Geometry
+    /// The smallest axis-aligned box containing all points of this mobject
+    /// and its submobjects, as its minimum and maximum corners.
+    fn bounding_box(&self) -> Option<(Vec3, Vec3)> {
+        let own = self
+            .points()
+            .iter()
+            .map(|&p| (p, p))
+            .reduce(|(min, max), (p, _)| (min.min(p), max.max(p)));
+        self.submobjects()
+            .iter()
+            .filter_map(|submobject| submobject.bounding_box())
+            .chain(own)
+            .reduce(|(min1, max1), (min2, max2)| (min1.min(min2), max1.max(max2)))
+    }
+    /// The center of the bounding box, or the origin for a mobject without
+    /// points.
+    fn center(&self) -> Vec3 {
+        self.bounding_box()
+            .map_or(Vec3::ZERO, |(min, max)| (min + max) / 2.0)
+    }
+
+    // Transforms
+    /// Moves every point of this mobject and its submobjects through `f`.
+    fn apply_function(&mut self, f: &dyn Fn(Vec3) -> Vec3) {
+        for p in self.points_mut() {
+            *p = f(*p);
+        }
+        for submobject in self.submobjects_mut() {
+            submobject.apply_function(f);
+        }
+    }
+    fn apply_transform(&mut self, transform: Mat4) {
+        self.apply_function(&|p| transform.transform_point3(p));
+    }
+    fn shift(&mut self, offset: Vec3) {
+        self.apply_function(&|p| p + offset);
+    }
+    /// Scales about the center.
+    fn scale(&mut self, factor: f32) {
+        self.scale_about_point(factor, self.center());
+    }
+    fn scale_about_point(&mut self, factor: f32, point: Vec3) {
+        self.apply_function(&|p| point + (p - point) * factor);
+    }
+    /// Rotates by `angle` radians around `axis` through the center,
+    /// counterclockwise when looking down the axis.
+    fn rotate(&mut self, angle: f32, axis: Vec3) {
+        self.rotate_about_point(angle, axis, self.center());
+    }
+    fn rotate_about_point(&mut self, angle: f32, axis: Vec3, point: Vec3) {
+        let rotation = Quat::from_axis_angle(axis.normalize(), angle);
+        self.apply_function(&|p| point + rotation * (p - point));
+    }
+    /// Mirrors the mobject by turning it half a turn around `axis` through
+    /// the center. Flipping around [`Vec3::Y`] mirrors left and right.
+    fn flip(&mut self, axis: Vec3) {
+        self.rotate(std::f32::consts::PI, axis);
+    }
+}
+
+/// Lets a [`SceneGraph`](crate::scene::graph::SceneGraph) hand mobjects back
+/// as their concrete type.
+pub trait AsAny: Any {
+    fn as_any(&self) -> &dyn Any;
+    fn as_any_mut(&mut self) -> &mut dyn Any;
+}
+impl<T: Any> AsAny for T {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
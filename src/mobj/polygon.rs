@@ -1,5 +1,155 @@
-use glam::Vec3;
+use std::f32::consts::{FRAC_PI_2, PI, TAU};
 
+use glam::{vec3, Vec3};
+
+use crate::{
+    color::{palette::WHITE, BlendMode, Color},
+    frame::{Frame, Mesh},
+    tess::{self, FillRule},
+};
+
+use super::{vectorized::VMobject, MObject};
+
+/// Line segments used to approximate each corner of a rounded rectangle.
+const CORNER_SEGMENTS: usize = 8;
+
+/// A filled outline through a list of vertices.
 pub struct Polygon {
     points: Vec<Vec3>,
-}
\ No newline at end of file
+    color: Color,
+    fill_rule: FillRule,
+    blend_mode: BlendMode,
+    z_index: i32,
+}
+impl Polygon {
+    /// A polygon through `points`, which is closed implicitly.
+    pub fn new(points: Vec<Vec3>) -> Self {
+        Self {
+            points,
+            color: WHITE,
+            fill_rule: FillRule::default(),
+            blend_mode: BlendMode::default(),
+            z_index: 0,
+        }
+    }
+    /// A regular polygon with `n` vertices on a circle of `radius` around the
+    /// origin. Odd polygons point up, even ones have a vertex on the right.
+    pub fn regular(n: usize, radius: f32) -> Self {
+        let start = if n % 2 == 1 { FRAC_PI_2 } else { 0.0 };
+        Self::new(
+            (0..n)
+                .map(|i| polar(radius, start + TAU * i as f32 / n as f32))
+                .collect(),
+        )
+    }
+    /// A star with `n` points, alternating between `outer_radius` and
+    /// `inner_radius`, with the first point facing up.
+    pub fn star(n: usize, outer_radius: f32, inner_radius: f32) -> Self {
+        Self::new(
+            (0..2 * n)
+                .map(|i| {
+                    let radius = if i % 2 == 0 {
+                        outer_radius
+                    } else {
+                        inner_radius
+                    };
+                    polar(radius, FRAC_PI_2 + PI * i as f32 / n as f32)
+                })
+                .collect(),
+        )
+    }
+    /// An axis-aligned rectangle centered on the origin.
+    pub fn rectangle(width: f32, height: f32) -> Self {
+        let (w, h) = (width / 2.0, height / 2.0);
+        Self::new(vec![
+            vec3(w, h, 0.0),
+            vec3(-w, h, 0.0),
+            vec3(-w, -h, 0.0),
+            vec3(w, -h, 0.0),
+        ])
+    }
+    /// An axis-aligned rectangle centered on the origin, with its corners
+    /// rounded off by arcs of `corner_radius`.
+    pub fn rounded_rectangle(width: f32, height: f32, corner_radius: f32) -> Self {
+        let (w, h) = (width / 2.0, height / 2.0);
+        let r = corner_radius.clamp(0.0, w.min(h));
+        let centers = [
+            vec3(w - r, h - r, 0.0),
+            vec3(-w + r, h - r, 0.0),
+            vec3(-w + r, -h + r, 0.0),
+            vec3(w - r, -h + r, 0.0),
+        ];
+        Self::new(
+            centers
+                .iter()
+                .enumerate()
+                .flat_map(|(corner, &center)| {
+                    (0..=CORNER_SEGMENTS).map(move |i| {
+                        let angle = FRAC_PI_2 * (corner as f32 + i as f32 / CORNER_SEGMENTS as f32);
+                        center + polar(r, angle)
+                    })
+                })
+                .collect(),
+        )
+    }
+
+    pub fn set_fill_rule(&mut self, fill_rule: FillRule) {
+        self.fill_rule = fill_rule;
+    }
+
+    /// Triangulates the area enclosed by the polygon.
+    pub fn triangulate(&self) -> Mesh {
+        let mut mesh = Mesh::new();
+        tess::fill(
+            &mut mesh,
+            std::slice::from_ref(&self.points),
+            self.fill_rule,
+            self.color.into(),
+        );
+        mesh.blend_mode = self.blend_mode;
+        mesh
+    }
+    /// The outline of this polygon as a closed path, filled with its color.
+    pub fn to_vmobject(&self) -> VMobject {
+        let mut vmobj = VMobject::new();
+        vmobj.set_points_as_corners(&self.points);
+        vmobj.close_path();
+        vmobj.fill_color = self.color;
+        vmobj.fill_rule = self.fill_rule;
+        vmobj.blend_mode = self.blend_mode;
+        vmobj
+    }
+}
+
+impl MObject for Polygon {
+    fn draw(&self, frame: &mut Frame) {
+        frame.push(self.triangulate());
+    }
+
+    fn points(&self) -> &[Vec3] {
+        &self.points
+    }
+    fn points_mut(&mut self) -> &mut [Vec3] {
+        &mut self.points
+    }
+
+    fn set_color(&mut self, color: Color) {
+        self.color = color;
+    }
+    fn set_opacity(&mut self, opacity: f32) {
+        self.color.a = opacity;
+    }
+    fn set_blend_mode(&mut self, blend_mode: BlendMode) {
+        self.blend_mode = blend_mode;
+    }
+    fn z_index(&self) -> i32 {
+        self.z_index
+    }
+    fn set_z_index(&mut self, z_index: i32) {
+        self.z_index = z_index;
+    }
+}
+
+fn polar(radius: f32, angle: f32) -> Vec3 {
+    vec3(radius * angle.cos(), radius * angle.sin(), 0.0)
+}
@@ -0,0 +1,689 @@
+use glam::Vec3;
+
+use crate::{
+    color::{palette::WHITE, BlendMode, Color, ColorSpace},
+    frame::{Frame, Mesh},
+    tess::{self, FillRule, StrokeStyle},
+};
+
+use super::MObject;
+
+/// Samples taken along each curve when it is flattened for drawing or
+/// measuring.
+const CURVE_SAMPLES: usize = 16;
+
+/// Distance under which two points are considered the same.
+const TOLERANCE: f32 = 1e-6;
+
+/// Mobjects that are a [`VMobject`] underneath, such as the shapes of
+/// [`geometry`](super::geometry), so path animations can work on them.
+pub trait Vectorized: MObject + AsRef<VMobject> + AsMut<VMobject> {}
+impl<M: MObject + AsRef<VMobject> + AsMut<VMobject>> Vectorized for M {}
+
+/// A mobject made of cubic Bezier curves.
+///
+/// Points are stored four per curve: start anchor, two handles and end
+/// anchor. A curve that doesn't start where the previous one ended starts a
+/// new subpath, and so does a curve that was explicitly started as one, even
+/// if it is continuous with the previous curve.
+#[derive(Clone, Debug)]
+pub struct VMobject {
+    points: Vec<Vec3>,
+    pub stroke_color: Color,
+    pub stroke_style: StrokeStyle,
+    /// Fill color. The default is fully transparent, i.e. no fill.
+    pub fill_color: Color,
+    pub fill_rule: FillRule,
+    pub blend_mode: BlendMode,
+    /// Start of a subpath that has no curves yet.
+    pending: Option<Vec3>,
+    /// Indices into `points` where a subpath starts even though the previous
+    /// curve ends there.
+    breaks: Vec<usize>,
+    z_index: i32,
+}
+impl VMobject {
+    pub fn new() -> Self {
+        Self {
+            points: vec![],
+            stroke_color: WHITE,
+            stroke_style: StrokeStyle::default(),
+            fill_color: Color::TRANSPARENT,
+            fill_rule: FillRule::default(),
+            blend_mode: BlendMode::default(),
+            pending: None,
+            breaks: vec![],
+            z_index: 0,
+        }
+    }
+    /// A polyline through `corners`.
+    pub fn from_corners(corners: &[Vec3]) -> Self {
+        let mut vmobj = Self::new();
+        vmobj.set_points_as_corners(corners);
+        vmobj
+    }
+
+    // Points
+    /// Replaces all points. The length of `points` must be a multiple of 4.
+    pub fn set_points(&mut self, points: Vec<Vec3>) {
+        assert_eq!(points.len() % 4, 0, "VMobject points must come in fours");
+        self.points = points;
+        self.breaks.clear();
+    }
+    /// Takes the points and subpaths of `other`, keeping this mobject's
+    /// style and z-index.
+    pub fn match_points(&mut self, other: &VMobject) {
+        self.points.clone_from(&other.points);
+        self.breaks.clone_from(&other.breaks);
+        self.pending = other.pending;
+    }
+    pub fn set_points_as_corners(&mut self, corners: &[Vec3]) {
+        self.clear_points();
+        for pair in corners.windows(2) {
+            self.points.extend(line_curve(pair[0], pair[1]));
+        }
+    }
+    pub fn clear_points(&mut self) {
+        self.points.clear();
+        self.pending = None;
+        self.breaks.clear();
+    }
+    pub fn has_points(&self) -> bool {
+        !self.points.is_empty()
+    }
+    pub fn start_point(&self) -> Option<Vec3> {
+        self.points.first().copied()
+    }
+    pub fn end_point(&self) -> Option<Vec3> {
+        self.points.last().copied()
+    }
+
+    // Path building
+    /// Starts a new subpath at `point`. The subpath only becomes visible once
+    /// a curve is added to it.
+    pub fn start_new_path(&mut self, point: Vec3) {
+        self.pending = Some(point);
+    }
+    pub fn add_cubic_bezier_curve_to(&mut self, handle1: Vec3, handle2: Vec3, anchor: Vec3) {
+        let start = self.begin_curve();
+        self.points.extend([start, handle1, handle2, anchor]);
+    }
+    pub fn add_line_to(&mut self, point: Vec3) {
+        let start = self.begin_curve();
+        self.points.extend(line_curve(start, point));
+    }
+    /// Connects the end of the current subpath back to its start.
+    pub fn close_path(&mut self) {
+        if let Some(start) = self.subpath_ranges().last().map(|r| self.points[r.start]) {
+            if !self.is_subpath_closed_at(self.points.len()) {
+                self.add_line_to(start);
+            }
+        }
+    }
+    /// Appends the curves of `other` as additional subpaths.
+    pub fn append_vectorized_mobject(&mut self, other: &VMobject) {
+        let offset = self.points.len();
+        if offset > 0 && !other.points.is_empty() {
+            self.breaks.push(offset);
+        }
+        self.breaks.extend(other.breaks.iter().map(|b| b + offset));
+        self.points.extend_from_slice(&other.points);
+    }
+
+    // Curves and subpaths
+    pub fn num_curves(&self) -> usize {
+        self.points.len() / 4
+    }
+    pub fn curves(&self) -> impl Iterator<Item = [Vec3; 4]> + '_ {
+        self.points
+            .chunks_exact(4)
+            .map(|c| [c[0], c[1], c[2], c[3]])
+    }
+    pub fn subpaths(&self) -> impl Iterator<Item = &[Vec3]> + '_ {
+        self.subpath_ranges().into_iter().map(|r| &self.points[r])
+    }
+    pub fn num_subpaths(&self) -> usize {
+        self.subpath_ranges().len()
+    }
+
+    // Measuring
+    /// Approximate arc length of the whole path.
+    pub fn length(&self) -> f32 {
+        self.curves().map(|c| curve_length(&c)).sum()
+    }
+    /// The point at `alpha` of the total arc length, with `alpha` in `[0, 1]`.
+    pub fn point_from_proportion(&self, alpha: f32) -> Option<Vec3> {
+        let curves: Vec<_> = self.curves().collect();
+        if curves.is_empty() {
+            return None;
+        }
+        let lengths: Vec<_> = curves.iter().map(curve_length).collect();
+        let total: f32 = lengths.iter().sum();
+        let (i, t) = locate(&lengths, alpha.clamp(0.0, 1.0) * total);
+        Some(bezier(curves[i], t))
+    }
+
+    // Partial paths
+    /// Becomes the part of `other` between the proportions `a` and `b` of its
+    /// total length, keeping the style of `other`.
+    pub fn pointwise_become_partial(&mut self, other: &VMobject, a: f32, b: f32) {
+        self.match_style(other);
+        self.points.clear();
+        self.breaks.clear();
+
+        let curves: Vec<_> = other.curves().collect();
+        if curves.is_empty() || a >= b {
+            return;
+        }
+        let lengths: Vec<_> = curves.iter().map(curve_length).collect();
+        let total: f32 = lengths.iter().sum();
+        let (ia, ta) = locate(&lengths, a.clamp(0.0, 1.0) * total);
+        let (ib, tb) = locate(&lengths, b.clamp(0.0, 1.0) * total);
+        self.breaks.extend(
+            other
+                .breaks
+                .iter()
+                .filter(|&&i| ia * 4 < i && i <= ib * 4)
+                .map(|i| i - ia * 4),
+        );
+
+        if ia == ib {
+            self.points.extend(partial_curve(curves[ia], ta, tb));
+        } else {
+            self.points.extend(partial_curve(curves[ia], ta, 1.0));
+            for curve in &curves[ia + 1..ib] {
+                self.points.extend(curve);
+            }
+            self.points.extend(partial_curve(curves[ib], 0.0, tb));
+        }
+    }
+    /// A new mobject containing the part of this one between the proportions
+    /// `a` and `b`.
+    pub fn get_subcurve(&self, a: f32, b: f32) -> VMobject {
+        let mut vmobj = VMobject::new();
+        vmobj.pointwise_become_partial(self, a, b);
+        vmobj
+    }
+
+    // Subdivision and alignment
+    /// Splits every curve into `n` curves of equal parameter length.
+    pub fn subdivide_curves(&mut self, n: usize) {
+        if n <= 1 {
+            return;
+        }
+        self.points = self
+            .curves()
+            .flat_map(|curve| subdivide_curve(curve, n))
+            .collect();
+        for b in &mut self.breaks {
+            *b *= n;
+        }
+    }
+    /// Adds `n` curves to the path without changing its shape, spreading the
+    /// new curves as evenly as possible over the existing ones. An empty path
+    /// gets `n` degenerate curves at its current point.
+    pub fn insert_n_curves(&mut self, n: usize) {
+        if !self.has_points() {
+            self.points = vec![self.current_point(); 4 * n];
+            return;
+        }
+        let factors = split_factors(self.num_curves(), n);
+        for b in &mut self.breaks {
+            *b = factors[..*b / 4].iter().sum::<usize>() * 4;
+        }
+        self.points = insert_n_curves_to_point_list(n, &self.points);
+    }
+    /// Makes this mobject and `other` have the same number of subpaths, and
+    /// the same number of curves in each pair of subpaths, so their points
+    /// can be interpolated one to one.
+    pub fn align_points(&mut self, other: &mut VMobject) {
+        if self.points.len() == other.points.len()
+            && self.subpath_ranges() == other.subpath_ranges()
+        {
+            return;
+        }
+        for vmobj in [&mut *self, &mut *other] {
+            if !vmobj.has_points() {
+                vmobj.points = vec![Vec3::ZERO; 4];
+            }
+        }
+
+        let subpaths1: Vec<_> = self.subpaths().collect();
+        let subpaths2: Vec<_> = other.subpaths().collect();
+        let n_subpaths = subpaths1.len().max(subpaths2.len());
+
+        // Every subpath boundary is kept as an explicit break, since padding
+        // subpaths are continuous with the subpath before them.
+        let mut new_points1 = vec![];
+        let mut new_points2 = vec![];
+        let mut breaks = vec![];
+        for n in 0..n_subpaths {
+            if n > 0 {
+                breaks.push(new_points1.len());
+            }
+            let sp1 = nth_subpath(&subpaths1, n);
+            let sp2 = nth_subpath(&subpaths2, n);
+            let diff1 = sp2.len().saturating_sub(sp1.len()) / 4;
+            let diff2 = sp1.len().saturating_sub(sp2.len()) / 4;
+            new_points1.extend(insert_n_curves_to_point_list(diff1, &sp1));
+            new_points2.extend(insert_n_curves_to_point_list(diff2, &sp2));
+        }
+        self.points = new_points1;
+        other.points = new_points2;
+        self.breaks = breaks.clone();
+        other.breaks = breaks;
+    }
+
+    // Style
+    pub fn set_stroke_width(&mut self, width: f32) {
+        self.stroke_style.width = width;
+    }
+    pub fn match_style(&mut self, other: &VMobject) {
+        self.stroke_color = other.stroke_color;
+        self.stroke_style = other.stroke_style.clone();
+        self.fill_color = other.fill_color;
+        self.fill_rule = other.fill_rule;
+        self.blend_mode = other.blend_mode;
+    }
+    /// Multiplies the opacity of both stroke and fill by `factor`.
+    pub fn fade(&mut self, factor: f32) {
+        self.stroke_color.a *= factor;
+        self.fill_color.a *= factor;
+    }
+    /// Becomes the mix of `start` and `end` at `alpha`, in points as well as
+    /// style, with colors mixed in `color_space`. Both must have the same
+    /// number of points; see [`align_points`](Self::align_points).
+    pub fn interpolate(
+        &mut self,
+        start: &VMobject,
+        end: &VMobject,
+        alpha: f32,
+        color_space: ColorSpace,
+    ) {
+        assert_eq!(start.points.len(), end.points.len());
+        self.points.clear();
+        self.points.extend(
+            start
+                .points
+                .iter()
+                .zip(&end.points)
+                .map(|(a, b)| a.lerp(*b, alpha)),
+        );
+        self.breaks.clone_from(&start.breaks);
+        self.match_style(if alpha < 1.0 { start } else { end });
+        self.stroke_color = start
+            .stroke_color
+            .interpolate(end.stroke_color, alpha, color_space);
+        self.stroke_style.width =
+            start.stroke_style.width + (end.stroke_style.width - start.stroke_style.width) * alpha;
+        self.fill_color = start
+            .fill_color
+            .interpolate(end.fill_color, alpha, color_space);
+    }
+
+    /// Moves and stretches the mobject so that the box from `from_min` to
+    /// `from_max` lands on the box from `to_min` to `to_max`. Flat axes are
+    /// only moved.
+    pub fn map_box(&mut self, (from_min, from_max): (Vec3, Vec3), (to_min, to_max): (Vec3, Vec3)) {
+        let from_size = from_max - from_min;
+        let to_size = to_max - to_min;
+        let scale = Vec3::select(
+            from_size.cmpgt(Vec3::splat(TOLERANCE)),
+            to_size / from_size,
+            Vec3::ONE,
+        );
+        let (from_center, to_center) = ((from_min + from_max) / 2.0, (to_min + to_max) / 2.0);
+        for p in &mut self.points {
+            *p = to_center + (*p - from_center) * scale;
+        }
+    }
+
+    fn current_point(&self) -> Vec3 {
+        self.pending
+            .or_else(|| self.end_point())
+            .unwrap_or(Vec3::ZERO)
+    }
+    /// Takes the start of the next curve, recording an explicit break if it
+    /// starts a new subpath.
+    fn begin_curve(&mut self) -> Vec3 {
+        let start = self.current_point();
+        if self.pending.take().is_some() && self.has_points() {
+            self.breaks.push(self.points.len());
+        }
+        start
+    }
+    fn subpath_ranges(&self) -> Vec<std::ops::Range<usize>> {
+        let mut ranges = vec![];
+        let mut start = 0;
+        for i in (4..self.points.len()).step_by(4) {
+            if self.points[i - 1].distance(self.points[i]) > TOLERANCE || self.breaks.contains(&i) {
+                ranges.push(start..i);
+                start = i;
+            }
+        }
+        if start < self.points.len() {
+            ranges.push(start..self.points.len());
+        }
+        ranges
+    }
+    fn is_subpath_closed_at(&self, end: usize) -> bool {
+        self.subpath_ranges()
+            .into_iter()
+            .find(|r| r.end == end)
+            .is_some_and(|r| self.points[r.start].distance(self.points[r.end - 1]) <= TOLERANCE)
+    }
+}
+impl AsRef<VMobject> for VMobject {
+    fn as_ref(&self) -> &VMobject {
+        self
+    }
+}
+impl AsMut<VMobject> for VMobject {
+    fn as_mut(&mut self) -> &mut VMobject {
+        self
+    }
+}
+impl Default for VMobject {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MObject for VMobject {
+    fn draw(&self, frame: &mut Frame) {
+        if self.fill_color.a > 0.0 {
+            let contours: Vec<_> = self.subpaths().map(flatten).collect();
+            let mut mesh = Mesh::new();
+            mesh.blend_mode = self.blend_mode;
+            tess::fill(&mut mesh, &contours, self.fill_rule, self.fill_color.into());
+            frame.push(mesh);
+        }
+
+        if self.stroke_color.a > 0.0 && self.stroke_style.width > 0.0 {
+            let mut mesh = Mesh::new();
+            mesh.blend_mode = self.blend_mode;
+            for subpath in self.subpaths() {
+                let points = flatten(subpath);
+                let closed =
+                    points.len() > 2 && points[0].distance(points[points.len() - 1]) <= TOLERANCE;
+
+                let mut lengths = Vec::with_capacity(points.len());
+                let mut total = 0.0;
+                for (i, p) in points.iter().enumerate() {
+                    if i > 0 {
+                        total += p.distance(points[i - 1]);
+                    }
+                    lengths.push(total);
+                }
+                let widths: Vec<_> = lengths
+                    .iter()
+                    .map(|l| {
+                        let alpha = if total > 0.0 { l / total } else { 0.0 };
+                        self.stroke_style.width_at(alpha, frame.pixel_size)
+                    })
+                    .collect();
+
+                tess::stroke(
+                    &mut mesh,
+                    &points,
+                    &widths,
+                    closed,
+                    &self.stroke_style,
+                    self.stroke_color.into(),
+                );
+            }
+            frame.push(mesh);
+        }
+    }
+
+    fn points(&self) -> &[Vec3] {
+        &self.points
+    }
+    fn points_mut(&mut self) -> &mut [Vec3] {
+        &mut self.points
+    }
+
+    /// Sets the stroke color, and the fill color unless the fill is
+    /// transparent.
+    fn set_color(&mut self, color: Color) {
+        self.stroke_color = color;
+        if self.fill_color.a > 0.0 {
+            self.fill_color = color;
+        }
+    }
+    /// Sets the stroke opacity, and the fill opacity unless the fill is
+    /// transparent.
+    fn set_opacity(&mut self, opacity: f32) {
+        self.stroke_color.a = opacity;
+        if self.fill_color.a > 0.0 {
+            self.fill_color.a = opacity;
+        }
+    }
+    fn set_blend_mode(&mut self, blend_mode: BlendMode) {
+        self.blend_mode = blend_mode;
+    }
+    fn z_index(&self) -> i32 {
+        self.z_index
+    }
+    fn set_z_index(&mut self, z_index: i32) {
+        self.z_index = z_index;
+    }
+}
+
+fn line_curve(start: Vec3, end: Vec3) -> [Vec3; 4] {
+    [
+        start,
+        start.lerp(end, 1.0 / 3.0),
+        start.lerp(end, 2.0 / 3.0),
+        end,
+    ]
+}
+
+fn bezier([p0, p1, p2, p3]: [Vec3; 4], t: f32) -> Vec3 {
+    let s = 1.0 - t;
+    p0 * (s * s * s) + p1 * (3.0 * s * s * t) + p2 * (3.0 * s * t * t) + p3 * (t * t * t)
+}
+
+fn curve_length(curve: &[Vec3; 4]) -> f32 {
+    (0..CURVE_SAMPLES)
+        .map(|i| {
+            let t0 = i as f32 / CURVE_SAMPLES as f32;
+            let t1 = (i + 1) as f32 / CURVE_SAMPLES as f32;
+            bezier(*curve, t0).distance(bezier(*curve, t1))
+        })
+        .sum()
+}
+
+/// Samples a subpath into a polyline.
+fn flatten(subpath: &[Vec3]) -> Vec<Vec3> {
+    let mut samples = vec![];
+    for c in subpath.chunks_exact(4) {
+        let curve = [c[0], c[1], c[2], c[3]];
+        let first = if samples.is_empty() { 0 } else { 1 };
+        samples.extend(
+            (first..=CURVE_SAMPLES).map(|i| bezier(curve, i as f32 / CURVE_SAMPLES as f32)),
+        );
+    }
+    samples
+}
+
+/// Finds the curve containing the point `length` along the path, and the
+/// parameter of that point within the curve.
+fn locate(lengths: &[f32], mut length: f32) -> (usize, f32) {
+    for (i, &curve_length) in lengths.iter().enumerate() {
+        if length <= curve_length || i == lengths.len() - 1 {
+            let t = if curve_length > 0.0 {
+                length / curve_length
+            } else {
+                1.0
+            };
+            return (i, t.clamp(0.0, 1.0));
+        }
+        length -= curve_length;
+    }
+    (0, 0.0)
+}
+
+/// Splits a curve at `t` with de Casteljau's algorithm.
+fn split_curve([p0, p1, p2, p3]: [Vec3; 4], t: f32) -> ([Vec3; 4], [Vec3; 4]) {
+    let p01 = p0.lerp(p1, t);
+    let p12 = p1.lerp(p2, t);
+    let p23 = p2.lerp(p3, t);
+    let p012 = p01.lerp(p12, t);
+    let p123 = p12.lerp(p23, t);
+    let mid = p012.lerp(p123, t);
+    ([p0, p01, p012, mid], [mid, p123, p23, p3])
+}
+
+/// The part of `curve` between the parameters `a` and `b`.
+fn partial_curve(curve: [Vec3; 4], a: f32, b: f32) -> [Vec3; 4] {
+    if b <= 0.0 {
+        return [curve[0]; 4];
+    }
+    let (left, _) = split_curve(curve, b);
+    let (_, right) = split_curve(left, a / b);
+    right
+}
+
+fn subdivide_curve(curve: [Vec3; 4], n: usize) -> impl Iterator<Item = Vec3> {
+    (0..n).flat_map(move |i| partial_curve(curve, i as f32 / n as f32, (i + 1) as f32 / n as f32))
+}
+
+fn insert_n_curves_to_point_list(n: usize, points: &[Vec3]) -> Vec<Vec3> {
+    let curr = points.len() / 4;
+    if n == 0 || curr == 0 {
+        return points.to_vec();
+    }
+    points
+        .chunks_exact(4)
+        .zip(split_factors(curr, n))
+        .flat_map(|(c, sf)| subdivide_curve([c[0], c[1], c[2], c[3]], sf))
+        .collect()
+}
+
+/// How many pieces each of `curr` curves is split into to add `n` curves.
+fn split_factors(curr: usize, n: usize) -> Vec<usize> {
+    let target = curr + n;
+    let mut factors = vec![0; curr];
+    for i in 0..target {
+        factors[i * curr / target] += 1;
+    }
+    factors
+}
+
+/// The `n`th subpath, or a degenerate one at the very end of the path if
+/// there are not enough subpaths. Callers must keep it apart from the
+/// subpath before it with an explicit break.
+fn nth_subpath(subpaths: &[&[Vec3]], n: usize) -> Vec<Vec3> {
+    match subpaths.get(n) {
+        Some(subpath) => subpath.to_vec(),
+        None => {
+            let last = subpaths.last().and_then(|sp| sp.last()).copied();
+            vec![last.unwrap_or(Vec3::ZERO); 4]
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn square(origin: Vec3) -> VMobject {
+        let corners = [Vec3::ZERO, Vec3::X, Vec3::X + Vec3::Y, Vec3::Y, Vec3::ZERO];
+        VMobject::from_corners(&corners.map(|p| p + origin))
+    }
+
+    #[test]
+    fn partial_counts() {
+        let sq = square(Vec3::ZERO);
+        let partial = |b| sq.get_subcurve(0.0, b);
+
+        assert_eq!(partial(0.0).points().len(), 0);
+        assert_eq!(partial(0.0).num_subpaths(), 0);
+
+        let half = partial(0.5);
+        assert_eq!(half.points().len(), 8);
+        assert_eq!(half.num_subpaths(), 1);
+        assert!(half.end_point().unwrap().distance(Vec3::X + Vec3::Y) < 1e-4);
+
+        let full = partial(1.0);
+        assert_eq!(full.points().len(), 16);
+        assert_eq!(full.num_subpaths(), 1);
+    }
+
+    #[test]
+    fn partial_keeps_breaks() {
+        let mut path = square(Vec3::ZERO);
+        path.append_vectorized_mobject(&square(Vec3::ZERO));
+        assert_eq!(path.num_subpaths(), 2);
+        assert_eq!(path.get_subcurve(0.0, 1.0).num_subpaths(), 2);
+        assert_eq!(path.get_subcurve(0.0, 0.25).num_subpaths(), 1);
+    }
+
+    #[test]
+    fn align_different_subpath_counts() {
+        let mut one = square(Vec3::ZERO);
+        let mut two = square(Vec3::ZERO);
+        two.append_vectorized_mobject(&square(Vec3::X * 3.0));
+        one.align_points(&mut two);
+
+        assert_eq!(one.points().len(), two.points().len());
+        assert_eq!(one.num_subpaths(), 2);
+        assert_eq!(two.num_subpaths(), 2);
+        assert_eq!(one.subpath_ranges(), two.subpath_ranges());
+
+        let mut mid = VMobject::new();
+        mid.interpolate(&one, &two, 0.5, ColorSpace::default());
+        assert_eq!(mid.num_subpaths(), 2);
+    }
+
+    #[test]
+    fn align_different_curve_counts() {
+        let mut line = VMobject::from_corners(&[Vec3::ZERO, Vec3::X]);
+        let mut sq = square(Vec3::ZERO);
+        line.align_points(&mut sq);
+
+        assert_eq!(line.points().len(), 16);
+        assert_eq!(sq.points().len(), 16);
+        assert_eq!(line.num_subpaths(), 1);
+        assert!(line.end_point().unwrap().distance(Vec3::X) < 1e-6);
+    }
+
+    #[test]
+    fn insert_into_empty() {
+        let mut empty = VMobject::new();
+        empty.insert_n_curves(3);
+        assert_eq!(empty.num_curves(), 3);
+        assert!(empty.points().iter().all(|&p| p == Vec3::ZERO));
+
+        let mut pending = VMobject::new();
+        pending.start_new_path(Vec3::X);
+        pending.insert_n_curves(2);
+        assert_eq!(pending.num_curves(), 2);
+        assert!(pending.points().iter().all(|&p| p == Vec3::X));
+
+        let mut sq = square(Vec3::ZERO);
+        let mut empty = VMobject::new();
+        empty.align_points(&mut sq);
+        assert_eq!(empty.points().len(), sq.points().len());
+    }
+
+    #[test]
+    fn explicit_new_path() {
+        let mut path = VMobject::new();
+        path.add_line_to(Vec3::X);
+        path.start_new_path(Vec3::X);
+        path.add_line_to(Vec3::Y);
+        assert_eq!(path.num_subpaths(), 2);
+    }
+
+    #[test]
+    fn corners_reset_pending() {
+        let mut path = VMobject::new();
+        path.start_new_path(Vec3::Z);
+        path.set_points_as_corners(&[Vec3::ZERO, Vec3::X]);
+        path.add_line_to(Vec3::Y);
+        assert_eq!(path.num_subpaths(), 1);
+        assert_eq!(path.points()[4], Vec3::X);
+    }
+}
@@ -1,3 +1,21 @@
-pub use crate::anim::{creation::Create, Animation};
-pub use crate::mobj::MObject;
+pub use crate::anim::{
+    background::ChangeBackground,
+    composition::{AnimationGroup, LaggedStart, Succession},
+    creation::{Create, Uncreate},
+    rate,
+    transform::{FadeTransform, ReplacementTransform, Transform},
+    Animation, AnimationSettings,
+};
+pub use crate::color::{BlendMode, Color, ColorSpace};
+pub use crate::mobj::{
+    geometry::{
+        Annulus, Arc, Arrow, ArrowTip, Circle, DashedLine, Dot, DoubleArrow, Ellipse, Line, Sector,
+        Square, TipShape,
+    },
+    group::Group,
+    layout::{Anchor, Grid, Layout, DL, DOWN, DR, IN, LEFT, ORIGIN, OUT, RIGHT, UL, UP, UR},
+    polygon::Polygon,
+    vectorized::{VMobject, Vectorized},
+    MObject,
+};
 pub use crate::scene::Scene;
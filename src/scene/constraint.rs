@@ -0,0 +1,490 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    error::Error,
+    fmt,
+    ops::{Add, Mul, Neg, Sub},
+};
+
+use cassowary::{
+    strength::{MEDIUM, REQUIRED, STRONG, WEAK},
+    AddConstraintError, RelationalOperator, Solver, Term, Variable,
+};
+use glam::{vec2, Vec2};
+
+use super::graph::{MobjectId, SceneGraph};
+
+/// Distance under which a solved position counts as unchanged.
+const TOLERANCE: f32 = 1e-4;
+
+/// A side or center of the bounding box of a mobject.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Attribute {
+    Left,
+    Right,
+    Bottom,
+    Top,
+    CenterX,
+    CenterY,
+}
+impl Attribute {
+    /// This attribute of the mobject behind `id`.
+    pub fn of(self, id: impl Into<MobjectId>) -> Expression {
+        Expression {
+            terms: vec![(id.into(), self, 1.0)],
+            constant: 0.0,
+        }
+    }
+}
+
+/// A sum of mobject [`Attribute`]s scaled by coefficients, plus a constant.
+#[derive(Clone, Debug, Default)]
+pub struct Expression {
+    terms: Vec<(MobjectId, Attribute, f32)>,
+    constant: f32,
+}
+impl Expression {
+    pub fn constant(value: f32) -> Self {
+        Self {
+            terms: vec![],
+            constant: value,
+        }
+    }
+
+    pub fn equals(self, rhs: impl Into<Expression>) -> Constraint {
+        Constraint::new(self - rhs.into(), RelationalOperator::Equal)
+    }
+    pub fn at_most(self, rhs: impl Into<Expression>) -> Constraint {
+        Constraint::new(self - rhs.into(), RelationalOperator::LessOrEqual)
+    }
+    pub fn at_least(self, rhs: impl Into<Expression>) -> Constraint {
+        Constraint::new(self - rhs.into(), RelationalOperator::GreaterOrEqual)
+    }
+}
+impl From<f32> for Expression {
+    fn from(value: f32) -> Self {
+        Self::constant(value)
+    }
+}
+impl<E: Into<Expression>> Add<E> for Expression {
+    type Output = Expression;
+    fn add(mut self, rhs: E) -> Expression {
+        let rhs = rhs.into();
+        self.terms.extend(rhs.terms);
+        self.constant += rhs.constant;
+        self
+    }
+}
+impl<E: Into<Expression>> Sub<E> for Expression {
+    type Output = Expression;
+    fn sub(self, rhs: E) -> Expression {
+        self + -rhs.into()
+    }
+}
+impl Mul<f32> for Expression {
+    type Output = Expression;
+    fn mul(mut self, rhs: f32) -> Expression {
+        for (_, _, coefficient) in &mut self.terms {
+            *coefficient *= rhs;
+        }
+        self.constant *= rhs;
+        self
+    }
+}
+impl Neg for Expression {
+    type Output = Expression;
+    fn neg(self) -> Expression {
+        self * -1.0
+    }
+}
+
+/// How hard the solver tries to satisfy a [`Constraint`] that conflicts
+/// with others.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Strength {
+    /// Always satisfied. Adding a required constraint that contradicts other
+    /// required ones fails.
+    #[default]
+    Required,
+    Strong,
+    Medium,
+    Weak,
+}
+impl Strength {
+    fn value(self) -> f64 {
+        match self {
+            Strength::Required => REQUIRED,
+            Strength::Strong => STRONG,
+            Strength::Medium => MEDIUM,
+            Strength::Weak => WEAK,
+        }
+    }
+}
+
+/// A linear equation or inequality between mobject attributes, such as
+/// `Left.of(b).equals(Right.of(a) + 0.5)`.
+#[derive(Clone, Debug)]
+pub struct Constraint {
+    /// Left hand side minus right hand side.
+    expression: Expression,
+    relation: RelationalOperator,
+    pub strength: Strength,
+}
+impl Constraint {
+    fn new(expression: Expression, relation: RelationalOperator) -> Self {
+        Self {
+            expression,
+            relation,
+            strength: Strength::default(),
+        }
+    }
+    pub fn with_strength(mut self, strength: Strength) -> Self {
+        self.strength = strength;
+        self
+    }
+}
+
+/// Identifies a constraint added to [`Constraints`], to remove it later.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct ConstraintId(usize);
+
+/// A required constraint contradicts the required constraints added before
+/// it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct UnsatisfiableConstraint;
+impl fmt::Display for UnsatisfiableConstraint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("required constraint conflicts with existing constraints")
+    }
+}
+impl Error for UnsatisfiableConstraint {}
+
+/// Solver variables of a constrained mobject.
+struct Tracked {
+    center: [Variable; 2],
+    size: [Variable; 2],
+    /// Current size, and the constraints holding the size variables to it.
+    size_constraints: Option<(Vec2, [cassowary::Constraint; 2])>,
+    /// Center the mobject was moved to by the last solve.
+    solved: Option<Vec2>,
+    /// Strength of the edit variables on the center.
+    edit_strength: Option<f64>,
+}
+impl Tracked {
+    fn new() -> Self {
+        Self {
+            center: [Variable::new(), Variable::new()],
+            size: [Variable::new(), Variable::new()],
+            size_constraints: None,
+            solved: None,
+            edit_strength: None,
+        }
+    }
+    fn term(&self, attribute: Attribute, coefficient: f64) -> [Term; 2] {
+        let [x, y] = self.center;
+        let [width, height] = self.size;
+        let (center, size, side) = match attribute {
+            Attribute::Left => (x, width, -0.5),
+            Attribute::Right => (x, width, 0.5),
+            Attribute::Bottom => (y, height, -0.5),
+            Attribute::Top => (y, height, 0.5),
+            Attribute::CenterX => (x, width, 0.0),
+            Attribute::CenterY => (y, height, 0.0),
+        };
+        [
+            Term {
+                variable: center,
+                coefficient,
+            },
+            Term {
+                variable: size,
+                coefficient: coefficient * side,
+            },
+        ]
+    }
+}
+
+/// Keeps mobjects positioned relative to each other by constraints on their
+/// bounding boxes, such as staying aligned or evenly spaced.
+///
+/// Constraints are solved again for every frame a [`Scene`](super::Scene)
+/// records, moving mobjects without resizing them. Mobjects moved since the
+/// previous frame, for example by an animation, take precedence, and the
+/// others move as little as the constraints allow. Bounding boxes are taken
+/// where the mobjects are drawn, with the transforms of the scene graph
+/// applied.
+pub struct Constraints {
+    solver: Solver,
+    mobjects: BTreeMap<MobjectId, Tracked>,
+    constraints: HashMap<ConstraintId, cassowary::Constraint>,
+    next_id: usize,
+}
+impl Constraints {
+    pub fn new() -> Self {
+        Self {
+            solver: Solver::new(),
+            mobjects: BTreeMap::new(),
+            constraints: HashMap::new(),
+            next_id: 0,
+        }
+    }
+
+    pub fn add(&mut self, constraint: Constraint) -> Result<ConstraintId, UnsatisfiableConstraint> {
+        let mut terms = vec![];
+        for &(id, attribute, coefficient) in &constraint.expression.terms {
+            let tracked = self.mobjects.entry(id).or_insert_with(Tracked::new);
+            terms.extend(tracked.term(attribute, coefficient as f64));
+        }
+        let constraint = cassowary::Constraint::new(
+            cassowary::Expression::new(terms, constraint.expression.constant as f64),
+            constraint.relation,
+            constraint.strength.value(),
+        );
+        match self.solver.add_constraint(constraint.clone()) {
+            Ok(()) => {}
+            Err(AddConstraintError::UnsatisfiableConstraint) => {
+                return Err(UnsatisfiableConstraint)
+            }
+            Err(error) => panic!("constraint solver failed: {error:?}"),
+        }
+
+        let id = ConstraintId(self.next_id);
+        self.next_id += 1;
+        self.constraints.insert(id, constraint);
+        Ok(id)
+    }
+    /// Removes a constraint. Mobjects stay where it put them.
+    pub fn remove(&mut self, id: ConstraintId) {
+        if let Some(constraint) = self.constraints.remove(&id) {
+            self.solver
+                .remove_constraint(&constraint)
+                .expect("constraint was added to the solver");
+        }
+    }
+
+    /// Adds all of `constraints`, or none of them if one fails.
+    fn add_all(
+        &mut self,
+        constraints: impl IntoIterator<Item = Constraint>,
+    ) -> Result<Vec<ConstraintId>, UnsatisfiableConstraint> {
+        let mut ids = vec![];
+        for constraint in constraints {
+            match self.add(constraint) {
+                Ok(id) => ids.push(id),
+                Err(error) => {
+                    for id in ids {
+                        self.remove(id);
+                    }
+                    return Err(error);
+                }
+            }
+        }
+        Ok(ids)
+    }
+
+    /// Makes `attribute` the same for all of `ids`. If that contradicts the
+    /// required constraints, nothing is added.
+    pub fn align(
+        &mut self,
+        ids: &[MobjectId],
+        attribute: Attribute,
+    ) -> Result<Vec<ConstraintId>, UnsatisfiableConstraint> {
+        self.add_all(
+            ids.windows(2)
+                .map(|pair| attribute.of(pair[0]).equals(attribute.of(pair[1]))),
+        )
+    }
+    /// Keeps the gaps between `ids` equal, taking them from left to right,
+    /// or from top to bottom if `vertical`. If that contradicts the required
+    /// constraints, nothing is added.
+    pub fn distribute(
+        &mut self,
+        ids: &[MobjectId],
+        vertical: bool,
+    ) -> Result<Vec<ConstraintId>, UnsatisfiableConstraint> {
+        let gap = |pair: &[MobjectId]| match vertical {
+            false => Attribute::Left.of(pair[1]) - Attribute::Right.of(pair[0]),
+            true => Attribute::Bottom.of(pair[0]) - Attribute::Top.of(pair[1]),
+        };
+        self.add_all(
+            ids.windows(3)
+                .map(|ids| gap(&ids[..2]).equals(gap(&ids[1..]))),
+        )
+    }
+
+    /// Moves the constrained mobjects of `graph` to satisfy the constraints.
+    pub fn solve(&mut self, graph: &mut SceneGraph) {
+        for (&id, tracked) in &mut self.mobjects {
+            let (min, max) = graph.bounding_box(id).unwrap_or_default();
+            let (center, size) = (((min + max) / 2.0).truncate(), (max - min).truncate());
+
+            if tracked.size_constraints.as_ref().map(|(s, _)| *s) != Some(size) {
+                if let Some((_, old)) = tracked.size_constraints.take() {
+                    for constraint in &old {
+                        self.solver.remove_constraint(constraint).ok();
+                    }
+                }
+                let constraints = [0, 1].map(|axis| {
+                    add_relaxed(&mut self.solver, tracked.size[axis], size[axis] as f64)
+                });
+                tracked.size_constraints = Some((size, constraints));
+            }
+
+            let moved = tracked
+                .solved
+                .is_some_and(|solved| solved.distance(center) > TOLERANCE);
+            let strength = if moved { STRONG } else { WEAK };
+            if tracked.edit_strength != Some(strength) {
+                for variable in tracked.center {
+                    if tracked.edit_strength.is_some() {
+                        self.solver.remove_edit_variable(variable).ok();
+                    }
+                    self.solver
+                        .add_edit_variable(variable, strength)
+                        .expect("edit strength is not required");
+                }
+                tracked.edit_strength = Some(strength);
+            }
+            for (variable, value) in tracked.center.into_iter().zip(center.to_array()) {
+                self.solver
+                    .suggest_value(variable, value as f64)
+                    .expect("center is an edit variable");
+            }
+        }
+
+        for (&id, tracked) in &mut self.mobjects {
+            let [x, y] = tracked.center.map(|v| self.solver.get_value(v) as f32);
+            let solved = vec2(x, y);
+            let (min, max) = graph.bounding_box(id).unwrap_or_default();
+            let center = ((min + max) / 2.0).truncate();
+            if solved.distance(center) > TOLERANCE {
+                let offset = (solved - center).extend(0.0);
+                graph.in_world_space(id, |mobject| mobject.shift(offset));
+            }
+            tracked.solved = Some(solved);
+        }
+    }
+}
+
+impl Default for Constraints {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Holds `variable` at `value`, or tries as hard as possible if that
+/// contradicts required constraints.
+fn add_relaxed(solver: &mut Solver, variable: Variable, value: f64) -> cassowary::Constraint {
+    let expression = |strength| {
+        cassowary::Constraint::new(
+            cassowary::Expression::new(
+                vec![Term {
+                    variable,
+                    coefficient: 1.0,
+                }],
+                -value,
+            ),
+            RelationalOperator::Equal,
+            strength,
+        )
+    };
+    let constraint = expression(REQUIRED);
+    if solver.add_constraint(constraint.clone()).is_ok() {
+        return constraint;
+    }
+    let constraint = expression(STRONG);
+    solver
+        .add_constraint(constraint.clone())
+        .expect("non-required constraints are always satisfiable");
+    constraint
+}
+
+#[cfg(test)]
+mod tests {
+    use glam::{vec3, Vec3};
+
+    use crate::{
+        mobj::{vectorized::VMobject, MObject},
+        scene::{Scene, SceneConfig},
+    };
+
+    use super::*;
+
+    fn mobjects<const N: usize>() -> [MobjectId; N] {
+        let mut graph = SceneGraph::new();
+        [(); N].map(|_| graph.insert(VMobject::new()).id())
+    }
+
+    #[test]
+    fn failed_align_adds_nothing() {
+        let [a, b, c] = mobjects();
+        let mut constraints = Constraints::new();
+        constraints
+            .add(Attribute::Left.of(b).equals(Attribute::Left.of(c) + 1.0))
+            .unwrap();
+
+        // a = b goes in before b = c fails, and must be taken out again
+        let result = constraints.align(&[a, b, c], Attribute::Left);
+        assert_eq!(result, Err(UnsatisfiableConstraint));
+        assert_eq!(constraints.constraints.len(), 1);
+        constraints
+            .add(Attribute::Left.of(a).equals(Attribute::Left.of(b) + 2.0))
+            .unwrap();
+    }
+
+    #[test]
+    fn failed_distribute_adds_nothing() {
+        let [a, b, c, d] = mobjects();
+        let mut constraints = Constraints::new();
+        let gap = |x, y| Attribute::Left.of(y) - Attribute::Right.of(x);
+        constraints.add(gap(b, c).equals(gap(c, d) + 1.0)).unwrap();
+
+        let result = constraints.distribute(&[a, b, c, d], false);
+        assert_eq!(result, Err(UnsatisfiableConstraint));
+        assert_eq!(constraints.constraints.len(), 1);
+        constraints.add(gap(a, b).equals(gap(b, c) + 3.0)).unwrap();
+    }
+
+    #[test]
+    fn follows_moving_anchor() {
+        let square = || VMobject::from_corners(&[Vec3::ZERO, Vec3::X, Vec3::ONE, Vec3::Y]);
+        let mut scene = Scene::new(SceneConfig::default());
+        let anchor = scene.add(square());
+        let follower = scene.add(square());
+        let frame_time = 1.0 / scene.config().frame_rate as f32;
+
+        scene
+            .constrain(
+                Attribute::Left
+                    .of(follower)
+                    .equals(Attribute::Right.of(anchor) + 0.5),
+            )
+            .unwrap();
+        scene
+            .constrain(
+                Attribute::Top
+                    .of(follower)
+                    .equals(Attribute::Bottom.of(anchor)),
+            )
+            .unwrap();
+        scene.wait(frame_time);
+        let expected = |anchor: Vec3| anchor + vec3(1.5, -1.0, 0.0);
+        assert!(scene
+            .get(follower)
+            .center()
+            .abs_diff_eq(expected(scene.get(anchor).center()), 1e-4));
+
+        // the anchor was moved last, so it stays where it was put
+        let start = scene.get(anchor).center();
+        for i in 1..=3 {
+            scene.get_mut(anchor).shift(vec3(1.0, 0.5, 0.0));
+            scene.wait(frame_time);
+            let anchor = scene.get(anchor).center();
+            assert!(anchor.abs_diff_eq(start + vec3(1.0, 0.5, 0.0) * i as f32, 1e-4));
+            assert!(scene
+                .get(follower)
+                .center()
+                .abs_diff_eq(expected(anchor), 1e-4));
+        }
+        assert_eq!(scene.frames().len(), 4);
+    }
+}
@@ -0,0 +1,401 @@
+use std::{fmt, hash::Hash, marker::PhantomData};
+
+use glam::{Mat4, Vec3};
+
+use crate::{color::Color, frame::Frame, mobj::MObject};
+
+/// Identifies a mobject owned by a [`SceneGraph`], regardless of its type.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct MobjectId(usize);
+
+/// A typed reference to a mobject owned by a [`SceneGraph`].
+///
+/// Handles stay valid for the whole lifetime of the scene, even after the
+/// mobject is removed from view.
+pub struct Handle<M> {
+    id: MobjectId,
+    _marker: PhantomData<fn() -> M>,
+}
+impl<M> Handle<M> {
+    pub fn id(self) -> MobjectId {
+        self.id
+    }
+}
+impl<M> Clone for Handle<M> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+impl<M> Copy for Handle<M> {}
+impl<M> PartialEq for Handle<M> {
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id
+    }
+}
+impl<M> Eq for Handle<M> {}
+impl<M> Hash for Handle<M> {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.id.hash(state)
+    }
+}
+impl<M> fmt::Debug for Handle<M> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Handle").field(&self.id.0).finish()
+    }
+}
+impl<M> From<Handle<M>> for MobjectId {
+    fn from(handle: Handle<M>) -> Self {
+        handle.id
+    }
+}
+
+struct Node {
+    mobject: Box<dyn MObject>,
+    /// Transform relative to the parent, or to the world for root nodes.
+    transform: Mat4,
+    parent: Option<MobjectId>,
+    /// Drawn after the parent, back to front.
+    children: Vec<MobjectId>,
+}
+
+/// Owns every mobject of a scene, their hierarchy and what is currently
+/// shown.
+///
+/// Shown root mobjects are drawn back to front in the order they were shown,
+/// each followed by its children, with those of a higher
+/// [z-index](MObject::z_index) in front of their siblings. A child is shown
+/// whenever its parent is.
+#[derive(Default)]
+pub struct SceneGraph {
+    nodes: Vec<Node>,
+    /// Root mobjects currently shown, back to front.
+    shown: Vec<MobjectId>,
+    background: Color,
+}
+impl SceneGraph {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Takes ownership of `mobject` without showing it.
+    pub fn insert<M: MObject>(&mut self, mobject: M) -> Handle<M> {
+        let id = MobjectId(self.nodes.len());
+        self.nodes.push(Node {
+            mobject: Box::new(mobject),
+            transform: Mat4::IDENTITY,
+            parent: None,
+            children: vec![],
+        });
+        Handle {
+            id,
+            _marker: PhantomData,
+        }
+    }
+    /// Takes ownership of `mobject` and shows it in front of everything else.
+    pub fn add<M: MObject>(&mut self, mobject: M) -> Handle<M> {
+        let handle = self.insert(mobject);
+        self.show(handle);
+        handle
+    }
+
+    pub fn get<M: MObject>(&self, handle: Handle<M>) -> &M {
+        self.nodes[handle.id.0]
+            .mobject
+            .as_ref()
+            .as_any()
+            .downcast_ref()
+            .expect("handle type matches its mobject")
+    }
+    pub fn get_mut<M: MObject>(&mut self, handle: Handle<M>) -> &mut M {
+        self.nodes[handle.id.0]
+            .mobject
+            .as_mut()
+            .as_any_mut()
+            .downcast_mut()
+            .expect("handle type matches its mobject")
+    }
+    /// The mobject behind `id`, without knowing its type.
+    pub fn mobject(&self, id: impl Into<MobjectId>) -> &dyn MObject {
+        self.nodes[id.into().0].mobject.as_ref()
+    }
+    pub fn mobject_mut(&mut self, id: impl Into<MobjectId>) -> &mut dyn MObject {
+        self.nodes[id.into().0].mobject.as_mut()
+    }
+
+    // Visibility and order
+    /// Whether `id` is drawn, either as a shown root or through its parent.
+    pub fn is_shown(&self, id: impl Into<MobjectId>) -> bool {
+        let id = id.into();
+        match self.nodes[id.0].parent {
+            Some(parent) => self.is_shown(parent),
+            None => self.shown.contains(&id),
+        }
+    }
+    /// Shows `id` in front of everything else. Children are shown in front of
+    /// their siblings instead.
+    pub fn show(&mut self, id: impl Into<MobjectId>) {
+        let id = id.into();
+        if self.nodes[id.0].parent.is_none() && !self.shown.contains(&id) {
+            self.shown.push(id);
+        } else {
+            self.bring_to_front(id);
+        }
+    }
+    /// Stops drawing `id`, detaching it from its parent if it has one. The
+    /// mobject itself is kept, so it can be shown again later.
+    pub fn remove(&mut self, id: impl Into<MobjectId>) {
+        let id = id.into();
+        self.detach(id);
+        self.shown.retain(|&shown| shown != id);
+    }
+    /// Shows `new` in the place of `old`, as a root or as a child of the same
+    /// parent, and removes `old`. Does nothing if `old` isn't shown or
+    /// attached to a parent.
+    pub fn replace(&mut self, old: impl Into<MobjectId>, new: impl Into<MobjectId>) {
+        let (old, new) = (old.into(), new.into());
+        if old == new || !self.siblings_mut(old).contains(&old) {
+            return;
+        }
+        assert!(
+            !self.is_ancestor(new, old),
+            "a mobject cannot replace its own descendant"
+        );
+        self.remove(new);
+        let siblings = self.siblings_mut(old);
+        let i = siblings.iter().position(|&id| id == old).unwrap();
+        siblings[i] = new;
+        self.nodes[new.0].parent = self.nodes[old.0].parent.take();
+    }
+    /// Moves `id` in front of its siblings.
+    pub fn bring_to_front(&mut self, id: impl Into<MobjectId>) {
+        let id = id.into();
+        let siblings = self.siblings_mut(id);
+        if let Some(i) = siblings.iter().position(|&s| s == id) {
+            siblings.remove(i);
+            siblings.push(id);
+        }
+    }
+    /// Moves `id` behind its siblings.
+    pub fn bring_to_back(&mut self, id: impl Into<MobjectId>) {
+        let id = id.into();
+        let siblings = self.siblings_mut(id);
+        if let Some(i) = siblings.iter().position(|&s| s == id) {
+            siblings.remove(i);
+            siblings.insert(0, id);
+        }
+    }
+
+    // Hierarchy
+    /// Makes `child` a child of `parent`, drawn in front of its siblings and
+    /// positioned relative to `parent`.
+    pub fn add_child(&mut self, parent: impl Into<MobjectId>, child: impl Into<MobjectId>) {
+        let (parent, child) = (parent.into(), child.into());
+        assert!(
+            !self.is_ancestor(child, parent),
+            "a mobject cannot be its own ancestor"
+        );
+        self.remove(child);
+        self.nodes[child.0].parent = Some(parent);
+        self.nodes[parent.0].children.push(child);
+    }
+    pub fn parent(&self, id: impl Into<MobjectId>) -> Option<MobjectId> {
+        self.nodes[id.into().0].parent
+    }
+    pub fn children(&self, id: impl Into<MobjectId>) -> &[MobjectId] {
+        &self.nodes[id.into().0].children
+    }
+
+    // Transforms
+    /// The transform of `id` relative to its parent.
+    pub fn transform(&self, id: impl Into<MobjectId>) -> Mat4 {
+        self.nodes[id.into().0].transform
+    }
+    pub fn set_transform(&mut self, id: impl Into<MobjectId>, transform: Mat4) {
+        self.nodes[id.into().0].transform = transform;
+    }
+    /// The transform of `id` relative to the world, including those inherited
+    /// from all of its ancestors.
+    pub fn world_transform(&self, id: impl Into<MobjectId>) -> Mat4 {
+        let node = &self.nodes[id.into().0];
+        match node.parent {
+            Some(parent) => self.world_transform(parent) * node.transform,
+            None => node.transform,
+        }
+    }
+    /// The bounding box of `id` where it is drawn, with its world transform
+    /// applied. Children in the graph are not included.
+    pub fn bounding_box(&self, id: impl Into<MobjectId>) -> Option<(Vec3, Vec3)> {
+        let id = id.into();
+        let transform = self.world_transform(id);
+        let mobject = self.mobject(id);
+        if transform == Mat4::IDENTITY {
+            return mobject.bounding_box();
+        }
+        transformed_bounds(mobject, transform)
+    }
+    /// Runs `f` on `id` with its points in world coordinates, the ones it is
+    /// drawn at, so that layout such as [`next_to`](crate::mobj::layout::Layout::next_to)
+    /// can place it against [`bounding_box`](Self::bounding_box)es of other
+    /// mobjects. A transform that flattens the mobject can't be undone, so
+    /// `f` gets its own coordinates then.
+    pub fn in_world_space(&mut self, id: impl Into<MobjectId>, f: impl FnOnce(&mut dyn MObject)) {
+        let id = id.into();
+        let transform = self.world_transform(id);
+        let mobject = self.mobject_mut(id);
+        if transform == Mat4::IDENTITY || transform.determinant() == 0.0 {
+            return f(mobject);
+        }
+        mobject.apply_transform(transform);
+        f(mobject);
+        mobject.apply_transform(transform.inverse());
+    }
+
+    // Background
+    /// Color behind all mobjects. Transparent unless set.
+    pub fn background(&self) -> Color {
+        self.background
+    }
+    pub fn set_background(&mut self, color: Color) {
+        self.background = color;
+    }
+
+    /// Draws every shown mobject.
+    pub fn draw(&self, frame: &mut Frame) {
+        for id in self.by_z_index(&self.shown) {
+            self.draw_node(id, Mat4::IDENTITY, frame);
+        }
+    }
+
+    fn draw_node(&self, id: MobjectId, parent_transform: Mat4, frame: &mut Frame) {
+        let node = &self.nodes[id.0];
+        let transform = parent_transform * node.transform;
+
+        let first = frame.meshes.len();
+        node.mobject.draw(frame);
+        if transform != Mat4::IDENTITY {
+            for mesh in &mut frame.meshes[first..] {
+                for vertex in &mut mesh.vertices {
+                    vertex.position = transform.transform_point3(vertex.position);
+                }
+            }
+        }
+
+        for child in self.by_z_index(&node.children) {
+            self.draw_node(child, transform, frame);
+        }
+    }
+    /// `ids` in drawing order, keeping their order within each z-index.
+    fn by_z_index(&self, ids: &[MobjectId]) -> Vec<MobjectId> {
+        let mut ids = ids.to_vec();
+        ids.sort_by_key(|id| self.nodes[id.0].mobject.z_index());
+        ids
+    }
+    fn detach(&mut self, id: MobjectId) {
+        if let Some(parent) = self.nodes[id.0].parent.take() {
+            self.nodes[parent.0].children.retain(|&c| c != id);
+        }
+    }
+    fn siblings_mut(&mut self, id: MobjectId) -> &mut Vec<MobjectId> {
+        match self.nodes[id.0].parent {
+            Some(parent) => &mut self.nodes[parent.0].children,
+            None => &mut self.shown,
+        }
+    }
+    fn is_ancestor(&self, ancestor: MobjectId, id: MobjectId) -> bool {
+        let mut current = Some(id);
+        while let Some(id) = current {
+            if id == ancestor {
+                return true;
+            }
+            current = self.nodes[id.0].parent;
+        }
+        false
+    }
+}
+
+/// The bounding box of `mobject` and its submobjects with `transform` applied
+/// to every point.
+fn transformed_bounds(mobject: &dyn MObject, transform: Mat4) -> Option<(Vec3, Vec3)> {
+    let own = mobject.points().iter().map(|&p| {
+        let p = transform.transform_point3(p);
+        (p, p)
+    });
+    let submobjects = mobject
+        .submobjects()
+        .iter()
+        .filter_map(|submobject| transformed_bounds(submobject.as_ref(), transform));
+    own.chain(submobjects)
+        .reduce(|(min1, max1), (min2, max2)| (min1.min(min2), max1.max(max2)))
+}
+
+#[cfg(test)]
+mod tests {
+    use glam::vec3;
+
+    use crate::mobj::{
+        layout::{Layout, RIGHT},
+        vectorized::VMobject,
+    };
+
+    use super::*;
+
+    #[test]
+    fn replace_takes_the_place_of_old() {
+        let mut graph = SceneGraph::new();
+        let [a, b, c] = [(); 3].map(|_| graph.add(VMobject::new()).id());
+        let new = graph.insert(VMobject::new()).id();
+        graph.replace(b, new);
+        assert_eq!(graph.shown, [a, new, c]);
+        assert!(!graph.is_shown(b));
+
+        let parent = graph.add(VMobject::new()).id();
+        graph.add_child(parent, b);
+        graph.replace(b, c);
+        assert_eq!(graph.children(parent), [c]);
+        assert_eq!(graph.parent(c), Some(parent));
+        assert_eq!(graph.parent(b), None);
+        assert_eq!(graph.shown, [a, new, parent]);
+    }
+
+    #[test]
+    fn world_bounding_box() {
+        let mut graph = SceneGraph::new();
+        let parent = graph.add(VMobject::new()).id();
+        let child = graph.insert(VMobject::from_corners(&[Vec3::ZERO, Vec3::ONE]));
+        graph.add_child(parent, child);
+        graph.set_transform(parent, Mat4::from_translation(Vec3::X * 2.0));
+        graph.set_transform(child, Mat4::from_scale(Vec3::splat(3.0)));
+
+        let (min, max) = graph.bounding_box(child).unwrap();
+        assert!(min.abs_diff_eq(vec3(2.0, 0.0, 0.0), 1e-5));
+        assert!(max.abs_diff_eq(vec3(5.0, 3.0, 3.0), 1e-5));
+        assert_eq!(
+            graph.mobject(child).bounding_box(),
+            Some((Vec3::ZERO, Vec3::ONE))
+        );
+    }
+
+    #[test]
+    fn layout_in_world_space() {
+        let mut graph = SceneGraph::new();
+        let anchor = graph.add(VMobject::from_corners(&[Vec3::ZERO, vec3(1.0, 1.0, 0.0)]));
+        let mobject = graph.add(VMobject::from_corners(&[Vec3::ZERO, vec3(1.0, 1.0, 0.0)]));
+        graph.set_transform(mobject, Mat4::from_scale(Vec3::splat(2.0)));
+
+        let target = graph.bounding_box(anchor).unwrap();
+        graph.in_world_space(mobject, |m| m.next_to(target, RIGHT, 0.5));
+        let (min, max) = graph.bounding_box(mobject).unwrap();
+        assert!(min.abs_diff_eq(vec3(1.5, -0.5, 0.0), 1e-5));
+        assert!(max.abs_diff_eq(vec3(3.5, 1.5, 0.0), 1e-5));
+    }
+
+    #[test]
+    fn replace_hidden_keeps_new() {
+        let mut graph = SceneGraph::new();
+        let old = graph.insert(VMobject::new()).id();
+        let new = graph.add(VMobject::new()).id();
+        graph.replace(old, new);
+        assert!(graph.is_shown(new));
+        assert!(!graph.is_shown(old));
+    }
+}
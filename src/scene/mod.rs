@@ -0,0 +1,185 @@
+use glam::{vec3, Vec3};
+
+use crate::{
+    anim::Animation,
+    color::{palette::BLACK, Color},
+    frame::Frame,
+    mobj::MObject,
+};
+
+use self::{
+    constraint::{Constraint, ConstraintId, Constraints, UnsatisfiableConstraint},
+    graph::{Handle, MobjectId, SceneGraph},
+};
+
+pub mod constraint;
+pub mod graph;
+
+/// Width of the visible area of a scene in world units. The visible height
+/// follows from the aspect ratio of the output.
+pub const FRAME_WIDTH: f32 = 14.0;
+
+/// Output parameters a scene is recorded for.
+#[derive(Clone, Copy, Debug)]
+pub struct SceneConfig {
+    pub frame_rate: u32,
+    /// Output width in pixels.
+    pub width: u32,
+    /// Output height in pixels.
+    pub height: u32,
+    /// Background the scene starts with, which may be transparent.
+    pub background: Color,
+}
+impl SceneConfig {
+    pub fn frame_height(&self) -> f32 {
+        FRAME_WIDTH * self.height as f32 / self.width as f32
+    }
+    /// The area seen by the camera, as its minimum and maximum corners.
+    pub fn frame_box(&self) -> (Vec3, Vec3) {
+        let half_size = vec3(FRAME_WIDTH, self.frame_height(), 0.0) / 2.0;
+        (-half_size, half_size)
+    }
+    /// Size of one output pixel in world units.
+    pub fn pixel_size(&self) -> f32 {
+        FRAME_WIDTH / self.width as f32
+    }
+}
+impl Default for SceneConfig {
+    fn default() -> Self {
+        Self {
+            frame_rate: 60,
+            width: 1920,
+            height: 1080,
+            background: BLACK,
+        }
+    }
+}
+
+/// Records frames of the mobjects in a [`SceneGraph`] as animations are
+/// played on them, keeping them in place according to its [`Constraints`].
+pub struct Scene {
+    graph: SceneGraph,
+    constraints: Constraints,
+    config: SceneConfig,
+    frames: Vec<Frame>,
+}
+
+impl Scene {
+    pub fn new(config: SceneConfig) -> Self {
+        let mut graph = SceneGraph::new();
+        graph.set_background(config.background);
+        Self {
+            graph,
+            constraints: Constraints::new(),
+            config,
+            frames: vec![],
+        }
+    }
+
+    /// Advances `animation` over its run time, recording one frame per
+    /// frame interval.
+    pub fn play<A: Animation>(&mut self, mut animation: A) {
+        animation.begin(&mut self.graph);
+        let frames = self.frame_count(animation.run_time());
+        for i in 0..frames {
+            animation.interpolate(&mut self.graph, i as f32 / frames as f32);
+            self.record();
+        }
+        animation.finish(&mut self.graph);
+    }
+
+    /// Holds the current state of the scene for `duration` seconds.
+    pub fn wait(&mut self, duration: f32) {
+        for _ in 0..self.frame_count(duration) {
+            self.record();
+        }
+    }
+
+    // Mobjects
+    /// Takes ownership of `mobject` without showing it, e.g. to use it as
+    /// the target of a transform.
+    pub fn insert<M: MObject>(&mut self, mobject: M) -> Handle<M> {
+        self.graph.insert(mobject)
+    }
+    /// Takes ownership of `mobject` and shows it in front of everything else.
+    pub fn add<M: MObject>(&mut self, mobject: M) -> Handle<M> {
+        self.graph.add(mobject)
+    }
+    /// Shows an inserted or removed mobject again, in front of everything
+    /// else.
+    pub fn show(&mut self, id: impl Into<MobjectId>) {
+        self.graph.show(id)
+    }
+    pub fn remove(&mut self, id: impl Into<MobjectId>) {
+        self.graph.remove(id)
+    }
+    pub fn bring_to_front(&mut self, id: impl Into<MobjectId>) {
+        self.graph.bring_to_front(id)
+    }
+    pub fn bring_to_back(&mut self, id: impl Into<MobjectId>) {
+        self.graph.bring_to_back(id)
+    }
+    pub fn get<M: MObject>(&self, handle: Handle<M>) -> &M {
+        self.graph.get(handle)
+    }
+    pub fn get_mut<M: MObject>(&mut self, handle: Handle<M>) -> &mut M {
+        self.graph.get_mut(handle)
+    }
+    pub fn graph(&self) -> &SceneGraph {
+        &self.graph
+    }
+    pub fn graph_mut(&mut self) -> &mut SceneGraph {
+        &mut self.graph
+    }
+
+    /// Changes the background from the next frame on. Use
+    /// [`ChangeBackground`](crate::anim::background::ChangeBackground) to
+    /// change it gradually.
+    pub fn set_background(&mut self, color: Color) {
+        self.graph.set_background(color)
+    }
+
+    // Constraints
+    /// Keeps `constraint` satisfied from now on, moving mobjects right away
+    /// if needed.
+    pub fn constrain(
+        &mut self,
+        constraint: Constraint,
+    ) -> Result<ConstraintId, UnsatisfiableConstraint> {
+        let id = self.constraints.add(constraint)?;
+        self.constraints.solve(&mut self.graph);
+        Ok(id)
+    }
+    pub fn constraints(&self) -> &Constraints {
+        &self.constraints
+    }
+    pub fn constraints_mut(&mut self) -> &mut Constraints {
+        &mut self.constraints
+    }
+
+    /// Draws every mobject currently shown.
+    pub fn frame(&self) -> Frame {
+        let mut frame = Frame::new(self.config.pixel_size(), self.graph.background());
+        self.graph.draw(&mut frame);
+        frame
+    }
+
+    /// All frames recorded so far, in playback order.
+    pub fn frames(&self) -> &[Frame] {
+        &self.frames
+    }
+
+    pub fn config(&self) -> &SceneConfig {
+        &self.config
+    }
+
+    fn frame_count(&self, duration: f32) -> usize {
+        (duration * self.config.frame_rate as f32).round().max(0.0) as usize
+    }
+
+    fn record(&mut self) {
+        self.constraints.solve(&mut self.graph);
+        let frame = self.frame();
+        self.frames.push(frame);
+    }
+}
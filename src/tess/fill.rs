@@ -0,0 +1,233 @@
+use glam::{vec3, Vec2, Vec3, Vec3Swizzles, Vec4};
+
+use crate::frame::Mesh;
+
+/// Distance under which two scanlines are merged.
+const EPSILON: f32 = 1e-5;
+
+/// Decides which regions of a set of outlines are inside, based on how many
+/// times the outlines wind around them.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum FillRule {
+    /// Inside wherever the winding number is not zero.
+    #[default]
+    NonZero,
+    /// Inside wherever the winding number is odd.
+    EvenOdd,
+}
+impl FillRule {
+    pub fn is_inside(self, winding: i32) -> bool {
+        match self {
+            FillRule::NonZero => winding != 0,
+            FillRule::EvenOdd => winding % 2 != 0,
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+struct Edge {
+    /// Endpoint with the smaller y.
+    lo: Vec2,
+    /// Endpoint with the larger y.
+    hi: Vec2,
+    /// +1 if the outline goes upwards along this edge, -1 otherwise.
+    winding: i32,
+}
+impl Edge {
+    fn x_at(&self, y: f32) -> f32 {
+        let t = (y - self.lo.y) / (self.hi.y - self.lo.y);
+        self.lo.x + (self.hi.x - self.lo.x) * t
+    }
+}
+
+/// Triangulates the region enclosed by `contours` in the xy plane and appends
+/// it to `mesh`.
+///
+/// Each contour is implicitly closed. Contours may be concave, intersect
+/// themselves or each other, and wind either way; `rule` decides what is
+/// filled. The z coordinate of the first point is used for the whole mesh.
+pub fn fill(mesh: &mut Mesh, contours: &[Vec<Vec3>], rule: FillRule, color: Vec4) {
+    let z = match contours.iter().find_map(|c| c.first()) {
+        Some(p) => p.z,
+        None => return,
+    };
+    let edges = edges(contours);
+
+    // Split the plane into horizontal slabs so that no two edges cross inside
+    // a slab. Within a slab, edges are then ordered by x, and the spans
+    // between consecutive edges are either fully inside or fully outside.
+    let mut ys: Vec<f32> = edges.iter().flat_map(|e| [e.lo.y, e.hi.y]).collect();
+    for (i, a) in edges.iter().enumerate() {
+        for b in &edges[i + 1..] {
+            if let Some(y) = intersection_y(a, b) {
+                ys.push(y);
+            }
+        }
+    }
+    ys.sort_by(|a, b| a.total_cmp(b));
+    ys.dedup_by(|a, b| (*a - *b).abs() < EPSILON);
+
+    let mut active: Vec<&Edge> = vec![];
+    for slab in ys.windows(2) {
+        let (y0, y1) = (slab[0], slab[1]);
+        let mid = (y0 + y1) / 2.0;
+
+        active.clear();
+        active.extend(edges.iter().filter(|e| e.lo.y < mid && mid < e.hi.y));
+        active.sort_by(|a, b| a.x_at(mid).total_cmp(&b.x_at(mid)));
+
+        let mut winding = 0;
+        for pair in active.windows(2) {
+            winding += pair[0].winding;
+            if rule.is_inside(winding) {
+                push_trapezoid(
+                    mesh,
+                    [
+                        vec3(pair[0].x_at(y0), y0, z),
+                        vec3(pair[1].x_at(y0), y0, z),
+                        vec3(pair[1].x_at(y1), y1, z),
+                        vec3(pair[0].x_at(y1), y1, z),
+                    ],
+                    color,
+                );
+            }
+        }
+    }
+}
+
+fn edges(contours: &[Vec<Vec3>]) -> Vec<Edge> {
+    let mut edges = vec![];
+    for contour in contours {
+        for (i, &a) in contour.iter().enumerate() {
+            let (a, b) = (a.xy(), contour[(i + 1) % contour.len()].xy());
+            // horizontal edges never cross a scanline
+            if (a.y - b.y).abs() < EPSILON {
+                continue;
+            }
+            edges.push(if a.y < b.y {
+                Edge {
+                    lo: a,
+                    hi: b,
+                    winding: 1,
+                }
+            } else {
+                Edge {
+                    lo: b,
+                    hi: a,
+                    winding: -1,
+                }
+            });
+        }
+    }
+    edges
+}
+
+/// The y coordinate where two edges properly cross, if they do.
+fn intersection_y(a: &Edge, b: &Edge) -> Option<f32> {
+    let r = a.hi - a.lo;
+    let s = b.hi - b.lo;
+    let denom = r.perp_dot(s);
+    if denom.abs() < f32::EPSILON {
+        return None;
+    }
+    let qp = b.lo - a.lo;
+    let t = qp.perp_dot(s) / denom;
+    let u = qp.perp_dot(r) / denom;
+    ((0.0..=1.0).contains(&t) && (0.0..=1.0).contains(&u)).then(|| a.lo.y + r.y * t)
+}
+
+fn push_trapezoid(mesh: &mut Mesh, corners: [Vec3; 4], color: Vec4) {
+    // spans between edges that touch at both scanlines have no area
+    if corners[0].distance(corners[1]) < EPSILON && corners[2].distance(corners[3]) < EPSILON {
+        return;
+    }
+    mesh.push_fan(&corners, color);
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f32::consts::{FRAC_PI_2, PI, TAU};
+
+    use super::*;
+
+    fn area(mesh: &Mesh) -> f32 {
+        mesh.indices
+            .chunks_exact(3)
+            .map(|t| {
+                let [a, b, c] = [0, 1, 2].map(|i| mesh.vertices[t[i] as usize].position);
+                (b - a).cross(c - a).length() / 2.0
+            })
+            .sum()
+    }
+
+    fn filled(contours: &[Vec<Vec3>], rule: FillRule) -> Mesh {
+        let mut mesh = Mesh::new();
+        fill(&mut mesh, contours, rule, Vec4::ONE);
+        mesh
+    }
+
+    fn rect(min: Vec2, max: Vec2) -> Vec<Vec3> {
+        vec![
+            vec3(min.x, min.y, 0.0),
+            vec3(max.x, min.y, 0.0),
+            vec3(max.x, max.y, 0.0),
+            vec3(min.x, max.y, 0.0),
+        ]
+    }
+
+    #[test]
+    fn star_rules() {
+        // a pentagram drawn in one stroke, so the inner pentagon winds twice
+        let star: Vec<_> = (0..5)
+            .map(|i| {
+                let angle = FRAC_PI_2 + i as f32 * 2.0 * TAU / 5.0;
+                vec3(angle.cos(), angle.sin(), 0.0)
+            })
+            .collect();
+        let inner_radius = (2.0 * PI / 5.0).cos() / (PI / 5.0).cos();
+        let star_area = 5.0 * inner_radius * (PI / 5.0).sin();
+        let pentagon_area = 2.5 * inner_radius * inner_radius * (2.0 * PI / 5.0).sin();
+
+        let non_zero = area(&filled(std::slice::from_ref(&star), FillRule::NonZero));
+        let even_odd = area(&filled(&[star], FillRule::EvenOdd));
+        assert!((non_zero - star_area).abs() < 1e-4, "{non_zero}");
+        assert!(
+            (even_odd - (star_area - pentagon_area)).abs() < 1e-4,
+            "{even_odd}"
+        );
+    }
+
+    #[test]
+    fn square_with_hole() {
+        let outer = rect(Vec2::ZERO, Vec2::splat(2.0));
+        let inner = rect(Vec2::splat(0.5), Vec2::splat(1.5));
+        let mut reversed = inner.clone();
+        reversed.reverse();
+
+        let holed = [outer.clone(), reversed];
+        assert!((area(&filled(&holed, FillRule::NonZero)) - 3.0).abs() < 1e-5);
+        assert!((area(&filled(&holed, FillRule::EvenOdd)) - 3.0).abs() < 1e-5);
+
+        // with the same winding, only even-odd leaves the hole open
+        let nested = [outer, inner];
+        assert!((area(&filled(&nested, FillRule::NonZero)) - 4.0).abs() < 1e-5);
+        assert!((area(&filled(&nested, FillRule::EvenOdd)) - 3.0).abs() < 1e-5);
+    }
+
+    #[test]
+    fn degenerate_input() {
+        let line = vec![Vec3::ZERO, Vec3::ONE, Vec3::ONE * 2.0];
+        let flat = vec![Vec3::ZERO, Vec3::X, Vec3::X * 2.0];
+        for contours in [
+            vec![],
+            vec![vec![]],
+            vec![vec![Vec3::ONE]],
+            vec![line],
+            vec![flat],
+        ] {
+            for rule in [FillRule::NonZero, FillRule::EvenOdd] {
+                assert!(filled(&contours, rule).is_empty());
+            }
+        }
+    }
+}
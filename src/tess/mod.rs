@@ -0,0 +1,9 @@
+//! Turning outlines into triangle meshes.
+
+pub mod fill;
+pub mod stroke;
+
+pub use self::{
+    fill::{fill, FillRule},
+    stroke::{stroke, LineCap, LineJoin, StrokeStyle, WidthUnits},
+};
@@ -0,0 +1,224 @@
+use std::f32::consts::PI;
+
+use glam::{Vec3, Vec4};
+
+use crate::frame::Mesh;
+
+/// Distance under which two consecutive points are merged.
+const EPSILON: f32 = 1e-6;
+
+/// Largest angle covered by a single triangle of a round join or cap.
+const ROUND_STEP: f32 = PI / 12.0;
+
+/// Shape drawn where two segments of a stroke meet.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum LineJoin {
+    /// Extends the outer edges until they meet, falling back to
+    /// [`Bevel`](Self::Bevel) past the miter limit.
+    #[default]
+    Miter,
+    Round,
+    Bevel,
+}
+
+/// Shape drawn at both ends of an open stroke.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum LineCap {
+    /// Ends flat at the end point.
+    #[default]
+    Butt,
+    Round,
+    /// Ends flat, half a stroke width past the end point.
+    Square,
+}
+
+/// Unit a stroke width is measured in.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum WidthUnits {
+    /// World units, so strokes scale along with the scene.
+    #[default]
+    World,
+    /// Output pixels, so strokes are equally thick at any scale.
+    Pixels,
+}
+
+#[derive(Clone, Debug)]
+pub struct StrokeStyle {
+    pub width: f32,
+    pub units: WidthUnits,
+    pub join: LineJoin,
+    pub cap: LineCap,
+    /// Longest miter allowed, as a multiple of the stroke width.
+    pub miter_limit: f32,
+    /// Multipliers of `width` at evenly spaced points along each subpath,
+    /// linearly interpolated in between. Empty means a constant width.
+    pub width_profile: Vec<f32>,
+}
+impl StrokeStyle {
+    /// The stroke width in world units at the proportion `alpha` of a path.
+    pub fn width_at(&self, alpha: f32, pixel_size: f32) -> f32 {
+        let width = match self.units {
+            WidthUnits::World => self.width,
+            WidthUnits::Pixels => self.width * pixel_size,
+        };
+        let factor = match self.width_profile.as_slice() {
+            [] => 1.0,
+            [factor] => *factor,
+            profile => {
+                let x = alpha.clamp(0.0, 1.0) * (profile.len() - 1) as f32;
+                let i = (x.floor() as usize).min(profile.len() - 2);
+                let t = x - i as f32;
+                profile[i] * (1.0 - t) + profile[i + 1] * t
+            }
+        };
+        width * factor
+    }
+}
+impl Default for StrokeStyle {
+    fn default() -> Self {
+        Self {
+            width: 0.04,
+            units: WidthUnits::World,
+            join: LineJoin::default(),
+            cap: LineCap::default(),
+            miter_limit: 4.0,
+            width_profile: vec![],
+        }
+    }
+}
+
+/// Appends a thick stroke along a polyline in the xy plane to `mesh`.
+///
+/// `widths` holds the world-space width at each point of `points`. Closed
+/// polylines are joined back to their start instead of getting caps; their
+/// last point should not repeat the first.
+pub fn stroke(
+    mesh: &mut Mesh,
+    points: &[Vec3],
+    widths: &[f32],
+    closed: bool,
+    style: &StrokeStyle,
+    color: Vec4,
+) {
+    assert_eq!(points.len(), widths.len());
+
+    let mut path: Vec<(Vec3, f32)> = Vec::with_capacity(points.len());
+    for (&p, &w) in points.iter().zip(widths) {
+        match path.last() {
+            Some(&(last, _)) if last.distance(p) < EPSILON => {}
+            _ => path.push((p, w / 2.0)),
+        }
+    }
+    let closed = closed && path.len() > 2;
+    if closed && path[0].0.distance(path[path.len() - 1].0) < EPSILON {
+        path.pop();
+    }
+    if path.len() < 2 {
+        return;
+    }
+
+    let len = path.len();
+    let segments = if closed { len } else { len - 1 };
+    let segment_normal = |i: usize| normal(path[i % len].0, path[(i + 1) % len].0);
+
+    for i in 0..segments {
+        let (p0, w0) = path[i];
+        let (p1, w1) = path[(i + 1) % len];
+        let n = segment_normal(i);
+        mesh.push_fan(&[p0 + n * w0, p0 - n * w0, p1 - n * w1, p1 + n * w1], color);
+    }
+
+    let joints = if closed { 0..len } else { 1..len - 1 };
+    for i in joints {
+        let (p, w) = path[i];
+        let n0 = segment_normal(i + len - 1);
+        let n1 = segment_normal(i);
+        join(mesh, p, n0, n1, w, style, color);
+    }
+
+    if !closed {
+        let (p, w) = path[0];
+        cap(mesh, p, -segment_normal(0), w, style.cap, color);
+        let (p, w) = path[len - 1];
+        cap(mesh, p, segment_normal(len - 2), w, style.cap, color);
+    }
+}
+
+/// Unit normal to the left of the direction from `a` to `b`.
+fn normal(a: Vec3, b: Vec3) -> Vec3 {
+    let d = (b - a).normalize_or_zero();
+    Vec3::new(-d.y, d.x, 0.0)
+}
+
+/// Fills the gap on the outer side of the corner at `p`, between a segment
+/// with normal `n0` and the following one with normal `n1`.
+fn join(
+    mesh: &mut Mesh,
+    p: Vec3,
+    n0: Vec3,
+    n1: Vec3,
+    half_width: f32,
+    style: &StrokeStyle,
+    color: Vec4,
+) {
+    let turn = n0.x * n1.y - n0.y * n1.x;
+    if turn.abs() < EPSILON && n0.dot(n1) > 0.0 {
+        // collinear, the segments already meet
+        return;
+    }
+    // turning left leaves the gap on the right
+    let side = if turn > 0.0 { -1.0 } else { 1.0 };
+    let a = p + n0 * half_width * side;
+    let b = p + n1 * half_width * side;
+
+    match style.join {
+        LineJoin::Bevel => mesh.push_fan(&[p, a, b], color),
+        LineJoin::Round => arc(
+            mesh,
+            p,
+            n0 * side,
+            turn.atan2(n0.dot(n1)),
+            half_width,
+            color,
+        ),
+        LineJoin::Miter => {
+            let bisector = (n0 + n1).normalize_or_zero();
+            let cos_half = bisector.dot(n0);
+            let miter = half_width / cos_half.max(EPSILON);
+            if bisector == Vec3::ZERO || miter > style.miter_limit * half_width {
+                mesh.push_fan(&[p, a, b], color);
+            } else {
+                mesh.push_fan(&[p, a, p + bisector * side * miter, b], color);
+            }
+        }
+    }
+}
+
+/// Closes off an open end at `p`. `n` is a unit normal of the end segment,
+/// chosen so that turning it clockwise points away from the stroke.
+fn cap(mesh: &mut Mesh, p: Vec3, n: Vec3, half_width: f32, cap: LineCap, color: Vec4) {
+    match cap {
+        LineCap::Butt => {}
+        LineCap::Square => {
+            let out = Vec3::new(n.y, -n.x, 0.0) * half_width;
+            let n = n * half_width;
+            mesh.push_fan(&[p + n, p - n, p - n + out, p + n + out], color);
+        }
+        LineCap::Round => arc(mesh, p, n, -PI, half_width, color),
+    }
+}
+
+/// A fan around `center`, starting in direction `from` and turning
+/// counterclockwise by `sweep` radians (clockwise if negative).
+fn arc(mesh: &mut Mesh, center: Vec3, from: Vec3, sweep: f32, radius: f32, color: Vec4) {
+    let start = from.y.atan2(from.x);
+    let steps = (sweep.abs() / ROUND_STEP).ceil().max(1.0) as usize;
+
+    let mut points = Vec::with_capacity(steps + 2);
+    points.push(center);
+    points.extend((0..=steps).map(|i| {
+        let angle = start + sweep * i as f32 / steps as f32;
+        center + Vec3::new(angle.cos(), angle.sin(), 0.0) * radius
+    }));
+    mesh.push_fan(&points, color);
+}